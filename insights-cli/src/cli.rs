@@ -1,10 +1,32 @@
-use clap::Parser;
+use chrono::{DateTime, Utc};
+use clap::{Args, Parser, Subcommand};
 
 /// Parse the output of `git log --numstat --date=rfc`
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 #[command(arg_required_else_help(true))]
+#[command(args_conflicts_with_subcommands(true))]
+#[command(subcommand_negates_reqs(true))]
 #[command(color(clap::ColorChoice::Auto))]
+struct App {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    args: Cli,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a SQL query over the `commits` table and the `commits_summary` view (one row per commit)
+    Query {
+        /// e.g. "SELECT author_name, SUM(added) AS added FROM commits GROUP BY author_name"
+        sql: String,
+        #[command(flatten)]
+        args: Cli,
+    },
+}
+
+#[derive(Args)]
 pub struct Cli {
     /// Path to the numstat.txt file
     /// or path to local/remote the git repositories.
@@ -13,15 +35,75 @@ pub struct Cli {
     /// Only including these years. e.g. --year 2022 --year 2023
     #[arg(short, long)]
     pub year: Vec<u32>,
+    /// Only including commits more recent than this date, on the --date field.
+    /// e.g. --since 2023-01-01 or --since 90.days.ago
+    #[arg(long, value_parser = numstat_parser::parse_date)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only including commits older than this date. e.g. --until "2023-03-31 23:59:59"
+    #[arg(long, value_parser = numstat_parser::parse_date)]
+    pub until: Option<DateTime<Utc>>,
+    /// Only read these branches instead of all of them. e.g. --branch main
+    #[arg(short, long)]
+    pub branch: Vec<String>,
+    /// Only read these revision ranges. e.g. --rev-range v1.0..v2.0
+    #[arg(long)]
+    pub rev_range: Vec<String>,
+    /// Merge commits to read, `only` with their changes against the first parent
+    #[arg(long, value_enum, default_value_t = MergesMode::Include)]
+    pub merges: MergesMode,
+    /// Levels of folders to search for git repositories, e.g. --max-depth 3 for ~/src/<org>/<team>/<repo>
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..), default_value_t = 1)]
+    pub max_depth: u32,
+    /// Skip these folders while searching for git repositories. e.g. --exclude archive --exclude "*-old"
+    #[arg(long)]
+    pub exclude: Vec<String>,
     /// Only including these author(s)
     #[arg(short, long)]
     pub author: Vec<String>,
     /// Filtered by ignore author(s)
     #[arg(long)]
     pub ignore_author: Vec<String>,
+    /// Credit commits and lines to the `Co-authored-by` co-authors as well, in the per-author queries
+    #[arg(long, overrides_with = "no_co_authors")]
+    pub co_authors: bool,
+    /// Do not credit the co-authors, even if the config does
+    #[arg(long, overrides_with = "co_authors")]
+    pub no_co_authors: bool,
     /// Filter out by extensions
     #[arg(short, long)]
     pub ignore_ext: Vec<String>,
+    /// Filter out binary files
+    #[arg(long, overrides_with = "no_ignore_binary")]
+    pub ignore_binary: bool,
+    /// Keep the binary files, even if the config ignores them
+    #[arg(long, overrides_with = "ignore_binary")]
+    pub no_ignore_binary: bool,
+    /// Team membership file, team name to the email or name patterns of its members.
+    /// e.g. Platform = ["me@duyet.net", "*@infra.example.com"]
+    #[arg(long)]
+    pub teams_file: Vec<std::path::PathBuf>,
+    /// Directory depth of the hotspot and ownership reports. e.g. --depth 2 for src/parser
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub depth: u32,
+    /// Share of the churn (in percent) the bus factor authors of a directory must cover
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub ownership_threshold: u8,
+    /// Write a draft CODEOWNERS from the ownership of the directories,
+    /// with the author emails as owners to be replaced by GitHub handles
+    #[arg(long)]
+    pub codeowners: Option<std::path::PathBuf>,
+    /// Ignore the commits with more lines added and deleted than this,
+    /// e.g. vendoring or generated code
+    #[arg(long)]
+    pub max_commit_lines: Option<u64>,
+    /// Timezone of the hour and weekday heatmap, e.g. UTC or +07:00,
+    /// defaults to the timezone of each commit
+    #[arg(long, value_parser = crate::heatmap::parse_timezone)]
+    pub timezone: Option<chrono::FixedOffset>,
+    /// Canonicalize the identities with a .mailmap file,
+    /// the .mailmap of each git repository is always applied
+    #[arg(long)]
+    pub mailmap: Vec<std::path::PathBuf>,
     /// Remap the author email. e.g. --remap-email "me@duyet.net<=5009534+duyet@users.noreply.github.com,lvduit08@gmail.com"
     #[arg(long)]
     pub remap_email: Vec<String>,
@@ -31,20 +113,90 @@ pub struct Cli {
     /// Remap the extension. e.g. --remap-ext "tsx=>ts"
     #[arg(long)]
     pub remap_ext: Vec<String>,
-    /// Output format
-    #[arg(short, long, value_enum, default_value_t = Output::None)]
-    pub output: Output,
+    /// Fail on the first commit or path that cannot be parsed,
+    /// instead of skipping it with a warning
+    #[arg(long)]
+    pub strict: bool,
+    /// Date used by the time-based queries and filters
+    #[arg(long, value_enum, default_value_t = DateField::Author)]
+    pub date: DateField,
+    /// Export the preprocessed per-file rows, the format is inferred
+    /// from the extension: .csv, .parquet or .ndjson
+    #[arg(long)]
+    pub export: Option<std::path::PathBuf>,
+    /// Path to the config file, defaults to the .girs.toml
+    /// of the git repositories or the current directory
+    #[arg(short, long)]
+    pub config: Option<std::path::PathBuf>,
+    /// Output format [default: none]
+    #[arg(short, long, value_enum)]
+    pub output: Option<Output>,
+    /// Layout of the queries in `--output json`
+    #[arg(long, value_enum, default_value_t = JsonLayout::Rows)]
+    pub json_layout: JsonLayout,
+    /// From the config, replace the built-in ignored extensions
+    #[arg(skip)]
+    pub default_ignore_ext: Option<Vec<String>>,
+    /// From the config, replace the built-in extension remaps
+    #[arg(skip)]
+    pub default_remap_ext: Option<Vec<String>>,
+    /// From the config, team name to the email or name patterns of its members
+    #[arg(skip)]
+    pub teams: crate::teams::Teams,
 }
 
-#[derive(clap::ValueEnum, Clone, Default)]
+#[derive(clap::ValueEnum, serde::Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
 pub enum Output {
     #[default]
     None,
     Json,
     Html,
+    Markdown,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Default)]
+pub enum MergesMode {
+    #[default]
+    Include,
+    Exclude,
+    Only,
 }
 
-// Parse the command line arguments
-pub fn parse() -> Cli {
-    Cli::parse()
+impl From<MergesMode> for numstat_parser::Merges {
+    fn from(mode: MergesMode) -> Self {
+        match mode {
+            MergesMode::Include => Self::Include,
+            MergesMode::Exclude => Self::Exclude,
+            MergesMode::Only => Self::Only,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Default)]
+pub enum JsonLayout {
+    /// One object per row
+    #[default]
+    Rows,
+    /// One array per column
+    Columns,
+}
+
+#[derive(clap::ValueEnum, Clone, Default, PartialEq)]
+pub enum DateField {
+    /// When the change was originally written
+    #[default]
+    Author,
+    /// When the change was applied, e.g. rebased, cherry-picked or merged by a bot
+    Commit,
+}
+
+// Parse the command line arguments, with the SQL of the `query` subcommand
+pub fn parse() -> (Cli, Option<String>) {
+    let app = App::parse();
+
+    match app.command {
+        Some(Command::Query { sql, args }) => (args, Some(sql)),
+        None => (app.args, None),
+    }
 }
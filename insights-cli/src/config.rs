@@ -0,0 +1,123 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{Cli, Output};
+use crate::teams;
+
+const CONFIG_FILE: &str = ".girs.toml";
+
+/// Shared configuration from `.girs.toml`, the keys have the same names as the flags:
+///
+/// ```toml
+/// ignore_author = ["dependabot[bot]"]
+/// ignore_ext = ["md"]
+/// remap_email = ["me@duyet.net<=5009534+duyet@users.noreply.github.com"]
+/// remap_ext = ["tsx=>ts"]
+/// mailmap = [".mailmap"]
+/// output = "markdown"
+///
+/// [teams]
+/// Platform = ["me@duyet.net", "*@infra.example.com"]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub author: Vec<String>,
+    pub ignore_author: Vec<String>,
+    pub ignore_ext: Vec<String>,
+    pub ignore_binary: bool,
+    pub co_authors: bool,
+    /// Relative to the config file
+    pub mailmap: Vec<PathBuf>,
+    pub remap_email: Vec<String>,
+    pub remap_name: Vec<String>,
+    pub remap_ext: Vec<String>,
+    /// Replace the built-in ignored extensions
+    pub default_ignore_ext: Option<Vec<String>>,
+    /// Replace the built-in extension remaps
+    pub default_remap_ext: Option<Vec<String>>,
+    pub output: Option<Output>,
+    /// Team name to the email or name patterns of its members
+    pub teams: teams::Teams,
+}
+
+impl Config {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Reading config `{}`", path.display()))?;
+        let mut config: Config = toml::from_str(&content)
+            .with_context(|| format!("Parsing config `{}`", path.display()))?;
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        config.mailmap = config.mailmap.iter().map(|p| dir.join(p)).collect();
+
+        Ok(config)
+    }
+}
+
+/// Find `.girs.toml` in the given repositories, then in the current directory
+fn discover(paths: &[PathBuf]) -> Option<PathBuf> {
+    paths
+        .iter()
+        .filter(|p| p.is_dir())
+        .map(|p| p.join(CONFIG_FILE))
+        .chain(std::iter::once(PathBuf::from(CONFIG_FILE)))
+        .find(|p| p.is_file())
+}
+
+/// Merge the config into the flags, the values from the flags come last
+/// so they take precedence in the remaps
+pub fn apply(args: &mut Cli) -> Result<()> {
+    let path = match &args.config {
+        Some(path) => path.clone(),
+        None => match discover(&args.path) {
+            Some(path) => path,
+            None => return Ok(()),
+        },
+    };
+
+    log::debug!("Using config {}", path.display());
+    let config = Config::from_file(&path)?;
+
+    let merge = |from: Vec<String>, flags: &mut Vec<String>| {
+        *flags = from.into_iter().chain(flags.drain(..)).collect();
+    };
+
+    merge(config.author, &mut args.author);
+    merge(config.ignore_author, &mut args.ignore_author);
+    merge(config.ignore_ext, &mut args.ignore_ext);
+    merge(config.remap_email, &mut args.remap_email);
+    merge(config.remap_name, &mut args.remap_name);
+    merge(config.remap_ext, &mut args.remap_ext);
+    args.mailmap = config
+        .mailmap
+        .into_iter()
+        .chain(args.mailmap.drain(..))
+        .collect();
+
+    // The config is only a default, `--no-...` turns it off
+    args.ignore_binary = !args.no_ignore_binary && (args.ignore_binary || config.ignore_binary);
+    args.co_authors = !args.no_co_authors && (args.co_authors || config.co_authors);
+    args.output = args.output.or(config.output);
+
+    if config.default_ignore_ext.is_some() {
+        args.default_ignore_ext = config.default_ignore_ext;
+    }
+    if config.default_remap_ext.is_some() {
+        args.default_remap_ext = config.default_remap_ext;
+    }
+
+    args.teams.extend(config.teams);
+
+    Ok(())
+}
+
+/// Add the teams from the `--teams-file` files
+pub fn apply_teams(args: &mut Cli) -> Result<()> {
+    for path in &args.teams_file {
+        args.teams.extend(teams::from_file(path)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anyhow::{bail, Context, Result};
+use polars::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::json;
+
+/// Write the per-file rows to `path`, the format is inferred from the extension:
+/// `.csv`, `.parquet` or `.ndjson`
+pub fn export(df: &mut DataFrame, path: &Path) -> Result<()> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    // Check the format before creating the file
+    if !matches!(ext.as_str(), "csv" | "parquet" | "ndjson" | "jsonl") {
+        bail!(
+            "Unsupported export format `{}`, expected .csv, .parquet or .ndjson",
+            path.display()
+        );
+    }
+
+    let file = File::create(path).with_context(|| format!("Creating `{}`", path.display()))?;
+
+    match ext.as_str() {
+        "csv" => CsvWriter::new(file).include_header(true).finish(df)?,
+        "parquet" => {
+            ParquetWriter::new(file).finish(df)?;
+        }
+        // Same values as --output json, one row per line
+        _ => {
+            let mut writer = BufWriter::new(file);
+            for i in 0..df.height() {
+                writeln!(writer, "{}", json::row(df, i)?)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
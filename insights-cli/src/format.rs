@@ -0,0 +1,25 @@
+use anyhow::Result;
+use polars::prelude::*;
+
+/// Value of a table cell, without the quotes of the strings
+pub fn cell(series: &Series, idx: usize) -> Result<String> {
+    Ok(match series.get(idx)? {
+        AnyValue::Utf8(s) => s.to_string(),
+        AnyValue::Null => String::new(),
+        v => v.to_string(),
+    })
+}
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Pipes and new lines would break the markdown tables
+pub fn escape_markdown(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
@@ -0,0 +1,143 @@
+use anyhow::Result;
+use chrono::{FixedOffset, Offset, Utc};
+use polars::prelude::*;
+use std::fmt::Write;
+
+/// Key of the heatmap in the queries
+pub const QUERY: &str = "commit_heatmap";
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MICROS_PER_HOUR: i64 = 3_600_000_000;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// Commits per weekday (rows) and hour of the day (columns `00` to `23`),
+/// in the timezone of each commit or in `timezone`
+pub fn compute(df: LazyFrame, timezone: Option<FixedOffset>) -> Result<DataFrame> {
+    let offset = match timezone {
+        Some(tz) => lit(tz.local_minus_utc() as i64),
+        None => col("tz_offset").cast(DataType::Int64),
+    };
+    let local = col("date").dt().timestamp(TimeUnit::Microseconds) + offset * lit(1_000_000i64);
+
+    // 1970-01-01 was a Thursday
+    let df = df
+        .with_columns([
+            ((local.clone() / lit(MICROS_PER_HOUR)) % lit(24i64)).alias("hour"),
+            ((local / lit(MICROS_PER_DAY) + lit(3i64)) % lit(7i64)).alias("weekday"),
+        ])
+        .group_by([col("weekday"), col("hour")])
+        .agg([col("commit").n_unique()])
+        .collect()?;
+
+    let mut grid = [[0u32; 24]; 7];
+    let weekdays = df.column("weekday")?.i64()?;
+    let hours = df.column("hour")?.i64()?;
+    let commits = df.column("commit")?.cast(&DataType::UInt32)?;
+    let commits = commits.u32()?;
+
+    for i in 0..df.height() {
+        if let (Some(weekday), Some(hour), Some(n)) =
+            (weekdays.get(i), hours.get(i), commits.get(i))
+        {
+            grid[weekday.rem_euclid(7) as usize][hour.rem_euclid(24) as usize] = n;
+        }
+    }
+
+    let mut columns = vec![Series::new("weekday", WEEKDAYS.to_vec())];
+    for hour in 0..24 {
+        let values = grid.iter().map(|row| row[hour]).collect::<Vec<_>>();
+        columns.push(Series::new(&format!("{:02}", hour), values));
+    }
+
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Parse a fixed timezone offset, e.g. `UTC`, `+07:00` or `-0500`
+pub fn parse_timezone(s: &str) -> Result<FixedOffset, String> {
+    if s.eq_ignore_ascii_case("utc") || s == "Z" {
+        return Ok(Utc.fix());
+    }
+
+    let invalid = || {
+        format!(
+            "Invalid timezone `{}`, expected UTC or an offset like +07:00",
+            s
+        )
+    };
+    let (sign, rest) = match s.chars().next() {
+        Some('+') => (1, &s[1..]),
+        Some('-') => (-1, &s[1..]),
+        _ => return Err(invalid()),
+    };
+
+    let digits = rest.replace(':', "");
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+    let minutes: i32 = digits[2..].parse().map_err(|_| invalid())?;
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// Grid of the heatmap for the terminal, shaded by the number of commits
+pub fn render_terminal(df: &DataFrame, color: bool) -> Result<String> {
+    const SHADES: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+    // Green scale of the 256 colors palette
+    const COLORS: [u8; 5] = [236, 22, 28, 34, 46];
+
+    let grid = cells(df)?;
+    let max = grid.iter().flatten().copied().max().unwrap_or(0);
+    let level = |n: u32| match (n, max) {
+        (0, _) | (_, 0) => 0,
+        (n, max) => ((n as f64 / max as f64 * 4.0).ceil() as usize).clamp(1, 4),
+    };
+
+    let mut out = String::new();
+
+    write!(out, "    ")?;
+    for hour in 0..24 {
+        write!(
+            out,
+            "{:<2}",
+            if hour % 3 == 0 {
+                format!("{:02}", hour)
+            } else {
+                String::new()
+            }
+        )?;
+    }
+    writeln!(out)?;
+
+    for (weekday, row) in WEEKDAYS.iter().zip(grid.iter()) {
+        write!(out, "{} ", weekday)?;
+        for n in row {
+            let level = level(*n);
+            if color {
+                write!(out, "\x1b[48;5;{}m  \x1b[0m", COLORS[level])?;
+            } else {
+                write!(out, "{}", SHADES[level].repeat(2))?;
+            }
+        }
+        writeln!(out)?;
+    }
+
+    writeln!(out, "max {} commits per hour", max)?;
+
+    Ok(out)
+}
+
+/// Commits per weekday and hour, from the output of [`compute`]
+pub fn cells(df: &DataFrame) -> Result<Vec<Vec<u32>>> {
+    let mut grid = vec![vec![0u32; 24]; df.height()];
+
+    for hour in 0..24 {
+        let values = df.column(&format!("{:02}", hour))?.u32()?;
+        for (i, row) in grid.iter_mut().enumerate() {
+            row[hour] = values.get(i).unwrap_or(0);
+        }
+    }
+
+    Ok(grid)
+}
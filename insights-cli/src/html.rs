@@ -0,0 +1,191 @@
+use anyhow::Result;
+use polars::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use crate::format::{cell, escape_html as escape};
+use crate::heatmap;
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem auto; max-width: 960px; color: #24292f; }
+h1 { border-bottom: 1px solid #d0d7de; padding-bottom: .3em; }
+h2 { margin-top: 2.5rem; }
+table { border-collapse: collapse; margin: 1rem 0; font-size: 14px; }
+th, td { border: 1px solid #d0d7de; padding: 4px 10px; text-align: left; }
+th { background: #f6f8fa; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+svg text { font-size: 12px; fill: #24292f; }
+svg rect { fill: #2da44e; }
+table.heatmap td { width: 24px; padding: 4px 0; text-align: center; font-size: 11px; }
+footer { margin-top: 3rem; color: #57606a; font-size: 12px; }
+"#;
+
+const BAR_HEIGHT: usize = 18;
+const LABEL_WIDTH: usize = 240;
+const CHART_WIDTH: usize = 480;
+
+/// Render every query as a self-contained HTML page (no external assets)
+pub fn render(heading: &HashMap<&str, &str>, query: &BTreeMap<&str, DataFrame>) -> Result<String> {
+    let mut out = String::new();
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>Git Insights</title>")?;
+    writeln!(out, "<style>{}</style>", STYLE)?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>Git Insights</h1>")?;
+
+    for (k, df) in query {
+        let title = heading.get(k).unwrap_or(k);
+
+        writeln!(out, "<section id=\"{}\">", escape(k))?;
+        writeln!(out, "<h2>{}</h2>", escape(title))?;
+        if *k == heatmap::QUERY {
+            out.push_str(&heatmap(df)?);
+        } else {
+            out.push_str(&chart(df)?);
+            out.push_str(&table(df)?);
+        }
+        writeln!(out, "</section>")?;
+    }
+
+    writeln!(out, "<footer>Generated by girs</footer>")?;
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")?;
+
+    Ok(out)
+}
+
+fn table(df: &DataFrame) -> Result<String> {
+    let mut out = String::new();
+
+    writeln!(out, "<table>")?;
+    write!(out, "<tr>")?;
+    for name in df.get_column_names() {
+        write!(out, "<th>{}</th>", escape(name))?;
+    }
+    writeln!(out, "</tr>")?;
+
+    for i in 0..df.height() {
+        write!(out, "<tr>")?;
+        for series in df.get_columns() {
+            let class = if series.dtype().is_numeric() {
+                " class=\"num\""
+            } else {
+                ""
+            };
+            write!(out, "<td{}>{}</td>", class, escape(&cell(series, i)?))?;
+        }
+        writeln!(out, "</tr>")?;
+    }
+
+    writeln!(out, "</table>")?;
+
+    Ok(out)
+}
+
+/// Horizontal bar chart as inline SVG.
+/// Bars use the last numeric column, labels are the remaining columns.
+fn chart(df: &DataFrame) -> Result<String> {
+    let columns = df.get_columns();
+
+    let value_col = match columns.iter().rposition(|s| s.dtype().is_numeric()) {
+        Some(idx) if df.height() > 1 => idx,
+        _ => return Ok(String::new()),
+    };
+
+    let values = columns[value_col].cast(&DataType::Float64)?;
+    let values = values.f64()?;
+    let max = values.max().unwrap_or(0.0);
+    if max <= 0.0 {
+        return Ok(String::new());
+    }
+
+    let height = df.height() * BAR_HEIGHT;
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "<svg width=\"{}\" height=\"{}\" role=\"img\">",
+        LABEL_WIDTH + CHART_WIDTH + 60,
+        height
+    )?;
+
+    for i in 0..df.height() {
+        let label = columns
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != value_col)
+            .map(|(_, s)| cell(s, i))
+            .collect::<Result<Vec<_>>>()?
+            .join(" / ");
+        let value = values.get(i).unwrap_or(0.0);
+        let width = (value / max * CHART_WIDTH as f64).round() as usize;
+        let y = i * BAR_HEIGHT;
+
+        writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+            LABEL_WIDTH - 6,
+            y + BAR_HEIGHT - 5,
+            escape(&label)
+        )?;
+        writeln!(
+            out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>{}: {}</title></rect>",
+            LABEL_WIDTH,
+            y + 2,
+            width,
+            BAR_HEIGHT - 4,
+            escape(&label),
+            value
+        )?;
+        writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\">{}</text>",
+            LABEL_WIDTH + width + 4,
+            y + BAR_HEIGHT - 5,
+            value
+        )?;
+    }
+
+    writeln!(out, "</svg>")?;
+
+    Ok(out)
+}
+
+/// Weekday and hour grid, shaded by the number of commits
+fn heatmap(df: &DataFrame) -> Result<String> {
+    let grid = heatmap::cells(df)?;
+    let weekdays = df.column("weekday")?;
+    let max = grid.iter().flatten().copied().max().unwrap_or(0).max(1);
+    let mut out = String::new();
+
+    writeln!(out, "<table class=\"heatmap\">")?;
+    write!(out, "<tr><th></th>")?;
+    for hour in 0..24 {
+        write!(out, "<th>{:02}</th>", hour)?;
+    }
+    writeln!(out, "</tr>")?;
+
+    for (i, row) in grid.iter().enumerate() {
+        write!(out, "<tr><th>{}</th>", escape(&cell(weekdays, i)?))?;
+        for n in row {
+            write!(
+                out,
+                "<td style=\"background: rgba(45, 164, 78, {:.2})\" title=\"{}\">{}</td>",
+                *n as f64 / max as f64,
+                n,
+                if *n > 0 { n.to_string() } else { String::new() }
+            )?;
+        }
+        writeln!(out, "</tr>")?;
+    }
+
+    writeln!(out, "</table>")?;
+
+    Ok(out)
+}
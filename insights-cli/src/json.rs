@@ -0,0 +1,135 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, SecondsFormat};
+use polars::prelude::*;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::cli::JsonLayout;
+
+/// Bumped on any breaking change of the layout below
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Render every query independently of the polars serde representation:
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "layout": "rows",
+///   "queries": {
+///     "commit_by_author": {
+///       "title": "Commit by author",
+///       "fields": [{ "name": "author_name", "type": "string" }, { "name": "commit", "type": "integer" }],
+///       "rows": [{ "author_name": "Duyet Le", "commit": 42 }]
+///     }
+///   }
+/// }
+/// ```
+///
+/// With `--json-layout columns`, `rows` is replaced by `"columns": { "author_name": [...], "commit": [...] }`.
+/// Types are `string`, `integer`, `number`, `boolean`, `datetime` (ISO-8601 in UTC), `date` and `array`.
+pub fn render(
+    heading: &HashMap<&str, &str>,
+    query: &BTreeMap<&str, DataFrame>,
+    layout: JsonLayout,
+) -> Result<Value> {
+    let mut queries = Map::new();
+
+    for (k, df) in query {
+        let title = heading.get(k).unwrap_or(k);
+
+        let fields = df
+            .get_columns()
+            .iter()
+            .map(|s| json!({ "name": s.name(), "type": type_name(s.dtype()) }))
+            .collect::<Vec<_>>();
+
+        let mut out = json!({ "title": title, "fields": fields });
+
+        match layout {
+            JsonLayout::Rows => {
+                let rows = (0..df.height())
+                    .map(|i| row(df, i))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                out["rows"] = Value::Array(rows);
+            }
+            JsonLayout::Columns => {
+                let mut columns = Map::new();
+                for s in df.get_columns() {
+                    let values = (0..s.len())
+                        .map(|i| s.get(i).map(value))
+                        .collect::<PolarsResult<Vec<_>>>()?;
+                    columns.insert(s.name().to_string(), Value::Array(values));
+                }
+                out["columns"] = Value::Object(columns);
+            }
+        }
+
+        queries.insert(k.to_string(), out);
+    }
+
+    Ok(json!({
+        "schema_version": SCHEMA_VERSION,
+        "layout": match layout {
+            JsonLayout::Rows => "rows",
+            JsonLayout::Columns => "columns",
+        },
+        "queries": queries,
+    }))
+}
+
+/// Row `i` of the DataFrame as an object, e.g. `{ "author_name": "Duyet", "commit": 42 }`
+pub fn row(df: &DataFrame, i: usize) -> PolarsResult<Value> {
+    let mut row = Map::new();
+    for s in df.get_columns() {
+        row.insert(s.name().to_string(), value(s.get(i)?));
+    }
+
+    Ok(Value::Object(row))
+}
+
+fn type_name(dtype: &DataType) -> &'static str {
+    match dtype {
+        DataType::Boolean => "boolean",
+        DataType::Utf8 => "string",
+        DataType::Datetime(_, _) => "datetime",
+        DataType::Date => "date",
+        DataType::List(_) => "array",
+        d if d.is_integer() => "integer",
+        d if d.is_float() => "number",
+        _ => "string",
+    }
+}
+
+fn value(v: AnyValue) -> Value {
+    match v {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => b.into(),
+        AnyValue::Utf8(s) => s.into(),
+        AnyValue::UInt8(n) => n.into(),
+        AnyValue::UInt16(n) => n.into(),
+        AnyValue::UInt32(n) => n.into(),
+        AnyValue::UInt64(n) => n.into(),
+        AnyValue::Int8(n) => n.into(),
+        AnyValue::Int16(n) => n.into(),
+        AnyValue::Int32(n) => n.into(),
+        AnyValue::Int64(n) => n.into(),
+        AnyValue::Float32(n) => n.into(),
+        AnyValue::Float64(n) => n.into(),
+        AnyValue::Datetime(n, unit, _) => {
+            let micros = match unit {
+                TimeUnit::Nanoseconds => n / 1_000,
+                TimeUnit::Microseconds => n,
+                TimeUnit::Milliseconds => n * 1_000,
+            };
+
+            DateTime::from_timestamp_micros(micros)
+                .map(|d| d.to_rfc3339_opts(SecondsFormat::Secs, true).into())
+                .unwrap_or(Value::Null)
+        }
+        AnyValue::Date(days) => NaiveDate::from_num_days_from_ce_opt(days + 719_163)
+            .map(|d| d.to_string().into())
+            .unwrap_or(Value::Null),
+        AnyValue::List(s) => Value::Array(s.iter().map(value).collect()),
+        v => v.to_string().into(),
+    }
+}
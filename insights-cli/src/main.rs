@@ -1,15 +1,36 @@
 mod cli;
+mod config;
+mod export;
+mod format;
+mod heatmap;
+mod html;
+mod json;
+mod markdown;
+mod ownership;
 mod preprocess;
+mod pull_requests;
+mod releases;
+mod sql;
+mod teams;
 
-use anyhow::{bail, Context, Result};
-use numstat_parser::{parse_from_path, Numstat};
+use anyhow::{Context, Result};
+use numstat_parser::{
+    parse_from_path_with, ConventionalCommit, LogOptions, Mailmap, Merges, Mode, Numstat,
+};
 use polars::frame::row::Row;
 use polars::prelude::*;
 use rayon::prelude::*;
 use std::collections::{BTreeMap, HashMap};
 use std::env;
 
-use crate::preprocess::preprocess;
+use crate::preprocess::{filter_commits, preprocess};
+use crate::releases::Releases;
+
+/// Number of files and directories in the hotspot report
+const HOTSPOT_LIMIT: u32 = 20;
+
+/// Number of the latest pull requests listed
+const PULL_REQUEST_LIMIT: u32 = 20;
 
 fn main() -> Result<()> {
     env::set_var("POLARS_FMT_TABLE_HIDE_COLUMN_DATA_TYPES", "1");
@@ -17,52 +38,87 @@ fn main() -> Result<()> {
     env::set_var("POLARS_FMT_MAX_COLS", "10");
 
     env_logger::init();
-    let args = cli::parse();
+    let (mut args, sql) = cli::parse();
+    config::apply(&mut args)?;
+    config::apply_teams(&mut args)?;
+
+    let mode = if args.strict {
+        Mode::Strict
+    } else {
+        Mode::Lenient
+    };
+
+    // Only read the history in the date range from git, which filters on the commit date.
+    // The author date is not after the commit date, so --since is safe to push down
+    // for both, but --until only for the commit date. The rows are filtered in preprocess again
+    let options = LogOptions {
+        since: args.since,
+        until: args.until.filter(|_| args.date == cli::DateField::Commit),
+        revisions: args.branch.iter().chain(&args.rev_range).cloned().collect(),
+        merges: args.merges.into(),
+        max_depth: args.max_depth as usize,
+        exclude: args.exclude.clone(),
+    };
+
+    // The queries run on the whole DataFrame, so the history is collected first
+    let parsed = parse_from_path_with(&args.path, mode, &options)
+        .with_context(|| format!("Parsing from {:?}", args.path))?;
+
+    for diagnostic in &parsed.diagnostics {
+        eprintln!("warning: {}", diagnostic);
+    }
+
+    let mut result: Vec<Numstat> = parsed.numstats;
+
+    // Git already did it, but not for the numstat files
+    let merges: Merges = args.merges.into();
+    result.retain(|n| merges.matches(!n.merges.is_empty()));
+
+    // Canonicalize the identities before building the DataFrame
+    if !args.mailmap.is_empty() {
+        let mut mailmap = Mailmap::default();
+        for path in &args.mailmap {
+            mailmap.extend(Mailmap::from_file(path)?);
+        }
+
+        result.par_iter_mut().for_each(|n| mailmap.apply(n));
+    }
 
-    let result: Vec<Numstat> =
-        parse_from_path(&args.path).with_context(|| format!("Parsing from {:?}", args.path))?;
+    // Release interval of each commit, from the tags
+    let releases = Releases::new(&result, merges);
 
-    // Map result to Vec<Row>
-    let rows = result
+    // Parsed before the rows as they borrow from it
+    let conventionals = result
         .par_iter()
-        .flat_map(|n| {
-            n.stats
-                .par_iter()
-                .map(|f| {
-                    Row::new(vec![
-                        AnyValue::Utf8(&n.commit),
-                        AnyValue::Datetime(
-                            n.date.timestamp_micros(),
-                            TimeUnit::Microseconds,
-                            &None,
-                        ),
-                        AnyValue::Utf8(&n.author.name),
-                        AnyValue::Utf8(&n.author.email),
-                        AnyValue::Utf8(&f.path),
-                        AnyValue::Utf8(&f.extension),
-                        AnyValue::UInt32(f.added),
-                        AnyValue::UInt32(f.deleted),
-                    ])
-                })
-                .collect::<Vec<Row>>()
-        })
-        .collect::<Vec<Row>>();
+        .map(|n| n.conventional())
+        .collect::<Vec<_>>();
 
-    let mut df = DataFrame::from_rows(&rows)?;
+    // One row per commit and file, credited to the author
+    let rows = to_rows(&result, &conventionals, &releases, false);
+    let df = to_dataframe(&rows)?;
 
-    // Change column names
-    df.rename("column_0", "commit")?
-        .rename("column_1", "date")?
-        .rename("column_2", "author_name")?
-        .rename("column_3", "author_email")?
-        .rename("column_4", "path")?
-        .rename("column_5", "extension")?
-        .rename("column_6", "added")?
-        .rename("column_7", "deleted")?;
+    // Same rows credited to the co-authors, only for the per-author queries
+    // so that the lines of a commit are not counted once per co-author
+    let co_author_rows = if args.co_authors {
+        to_rows(&result, &conventionals, &releases, true)
+    } else {
+        vec![]
+    };
+    let authors_df = if co_author_rows.is_empty() {
+        df.clone()
+    } else {
+        df.vstack(&to_dataframe(&co_author_rows)?)?
+    };
 
     // Print the DataFrame
     log::debug!("{}\n", preprocess(df.clone(), &args).collect()?);
 
+    // Export the rows before aggregating
+    if let Some(path) = &args.export {
+        let mut rows = preprocess(df.clone(), &args).collect()?;
+        export::export(&mut rows, path).with_context(|| format!("Exporting to {:?}", path))?;
+    }
+
     let mut heading = HashMap::new();
     let mut query: BTreeMap<&str, DataFrame> = BTreeMap::new();
 
@@ -73,131 +129,486 @@ fn main() -> Result<()> {
         };
     }
 
-    // Query: data summary.
-    query!(
-        "summary",
-        "Summary",
-        preprocess(df.clone(), &args)
+    // Query: ad-hoc SQL from `girs query`, instead of the default queries
+    if let Some(sql) = &sql {
+        query!(
+            "query",
+            "Query",
+            sql::execute(preprocess(df.clone(), &args), sql)?
+        );
+    } else {
+        // Query: data summary.
+        query!(
+            "summary",
+            "Summary",
+            preprocess(df.clone(), &args)
+                .select([
+                    col("author_name").n_unique().alias("author_count"),
+                    col("commit").n_unique().alias("commit_count"),
+                    col("author_name").list().0.alias("authors"),
+                    col("extension").list().0.alias("extensions"),
+                    col("added").sum(),
+                    col("deleted").sum(),
+                    col("date").max().alias("last commit"),
+                ])
+                .collect()?
+        );
+
+        // Query: How many lines of code were added per author?
+        query!(
+            "commit_by_author",
+            "Commit by author",
+            preprocess(authors_df.clone(), &args)
+                .group_by([col("author_name")])
+                .agg([col("commit").n_unique()])
+                .sort_by_exprs(&[col("commit")], [true], false, true)
+                .collect()?
+        );
+
+        // Query: Total commits by month
+        query!(
+            "commit_by_month",
+            "Commit by month",
+            preprocess(df.clone(), &args)
+                .group_by([col("year_month")])
+                .agg([col("commit").n_unique()])
+                .sort_by_exprs(&[col("year_month")], [false], false, true)
+                .collect()?
+        );
+
+        // Query: Commit by author by date, convert date to YYYY-MM
+        query!(
+            "commit_by_author_by_month",
+            "Commit by author by month",
+            preprocess(authors_df.clone(), &args)
+                .group_by([col("author_name"), col("year_month")])
+                .agg([col("commit").n_unique()])
+                .sort_by_exprs(
+                    &[col("author_name"), col("commit")],
+                    [false, true],
+                    false,
+                    true
+                )
+                .collect()?
+        );
+
+        // Query: lines added, deleted and net per author
+        query!(
+            "lines_by_author",
+            "Lines by author",
+            preprocess(authors_df.clone(), &args)
+                .group_by([col("author_name")])
+                .agg(lines())
+                .sort_by_exprs(&[col("added")], [true], false, true)
+                .collect()?
+        );
+
+        // Query: lines added, deleted and net per month
+        query!(
+            "lines_by_month",
+            "Lines by month",
+            preprocess(df.clone(), &args)
+                .group_by([col("year_month")])
+                .agg(lines())
+                .sort_by_exprs(&[col("year_month")], [false], false, true)
+                .collect()?
+        );
+
+        // Query: lines added, deleted and net per language
+        query!(
+            "lines_by_language",
+            "Lines by language",
+            preprocess(df.clone(), &args)
+                .group_by([col("extension").alias("language")])
+                .agg(lines())
+                .sort_by_exprs(&[col("added")], [true], false, true)
+                .collect()?
+        );
+
+        // Query: Top languages
+        query!(
+            "top_languages",
+            "Top languages",
+            preprocess(df.clone(), &args)
+                .group_by([col("extension").alias("language")])
+                .agg([col("commit").n_unique()])
+                .sort_by_exprs(&[col("commit")], [true], false, true)
+                .limit(5)
+                .collect()?
+        );
+
+        // Query: files touched by the most commits, with their churn and authors
+        query!(
+            "hotspot_files",
+            "Hotspot files",
+            preprocess(df.clone(), &args)
+                .group_by([col("path")])
+                .agg([
+                    col("commit").n_unique().alias("commits"),
+                    (col("added") + col("deleted")).sum().alias("churn"),
+                    col("author_name").n_unique().alias("authors"),
+                ])
+                .sort_by_exprs(&[col("commits"), col("churn")], [true, true], false, true)
+                .limit(HOTSPOT_LIMIT)
+                .collect()?
+        );
+
+        // Query: same for the directories, up to --depth levels
+        query!(
+            "hotspot_directories",
+            "Hotspot directories",
+            preprocess(df.clone(), &args)
+                .group_by([col("directory")])
+                .agg([
+                    col("commit").n_unique().alias("commits"),
+                    (col("added") + col("deleted")).sum().alias("churn"),
+                    col("author_name").n_unique().alias("authors"),
+                    col("path").n_unique().alias("files"),
+                ])
+                .sort_by_exprs(&[col("commits"), col("churn")], [true, true], false, true)
+                .limit(HOTSPOT_LIMIT)
+                .collect()?
+        );
+
+        // Query: owner and bus factor of the directories
+        let ownership = ownership::compute(
+            preprocess(df.clone(), &args),
+            args.ownership_threshold as f64 / 100.0,
+        )?;
+        query!(
+            "ownership",
+            "Ownership",
+            ownership::to_dataframe(&ownership)?
+        );
+
+        if let Some(path) = &args.codeowners {
+            std::fs::write(path, ownership::codeowners(&ownership)?)
+                .with_context(|| format!("Writing {:?}", path))?;
+        }
+
+        // Query: contribution by team, only with the team definitions
+        if !args.teams.is_empty() {
+            query!(
+                "commit_by_team",
+                "Commit by team",
+                preprocess(df.clone(), &args)
+                    .group_by([col("team")])
+                    .agg([
+                        col("commit").n_unique(),
+                        col("author_name").n_unique().alias("authors"),
+                        col("added").sum(),
+                        col("deleted").sum(),
+                    ])
+                    .sort_by_exprs(&[col("commit")], [true], false, true)
+                    .collect()?
+            );
+
+            query!(
+                "commit_by_team_by_month",
+                "Commit by team by month",
+                preprocess(df.clone(), &args)
+                    .group_by([col("team"), col("year_month")])
+                    .agg([
+                        col("commit").n_unique(),
+                        col("added").sum(),
+                        col("deleted").sum(),
+                    ])
+                    .sort_by_exprs(
+                        &[col("team"), col("year_month")],
+                        [false, false],
+                        false,
+                        true
+                    )
+                    .collect()?
+            );
+        }
+
+        // Query: Top commit by weekday
+        query!(
+            "commit_by_weekday",
+            "Commit by weekday",
+            preprocess(df.clone(), &args)
+                .with_column(col("date").dt().weekday().alias("n"))
+                .with_column(col("date").dt().strftime("%A").alias("weekday"))
+                .group_by([col("n"), col("weekday")])
+                .agg([col("commit").n_unique()])
+                .sort_by_exprs(&[col("n")], [false], false, true)
+                .collect()?
+        );
+
+        // Query: conventional commit types per month, e.g. the feat/fix/chore ratios
+        query!(
+            "commit_by_type_by_month",
+            "Commit by type by month",
+            preprocess(df.clone(), &args)
+                .with_column(
+                    when(col("commit_type").eq(lit("")))
+                        .then(lit("(none)"))
+                        .otherwise(col("commit_type"))
+                        .alias("type"),
+                )
+                .group_by([col("year_month"), col("type")])
+                .agg([col("commit").n_unique()])
+                .sort_by_exprs(
+                    &[col("year_month"), col("commit")],
+                    [false, true],
+                    false,
+                    true
+                )
+                .collect()?
+        );
+
+        // Query: breaking changes per release, the latest first
+        query!(
+            "breaking_by_release",
+            "Breaking changes by release",
+            preprocess(df.clone(), &args)
+                .filter(col("breaking"))
+                .group_by([col("release")])
+                .agg([
+                    col("commit").n_unique().alias("breaking_changes"),
+                    col("commit_date").max().alias("last_commit"),
+                ])
+                .sort_by_exprs(&[col("last_commit")], [true], false, true)
+                .collect()?
+        );
+
+        // Query: changelog summary of each release interval, only with tags
+        if !releases.is_empty() {
+            query!(
+                "releases",
+                "Releases",
+                releases::report(preprocess(df.clone(), &args))?
+            );
+        }
+
+        // Query: activity of each branch, by the branch each commit was reached from,
+        // only with the source of the commits (not in the numstat files)
+        if result.iter().any(|n| !n.source.is_empty()) {
+            query!(
+                "commit_by_branch",
+                "Commit by branch",
+                preprocess(df.clone(), &args)
+                    .group_by([col("branch")])
+                    .agg([
+                        col("commit").n_unique(),
+                        col("author_name").n_unique().alias("authors"),
+                        col("commit_date").max().alias("last_commit"),
+                    ])
+                    .sort_by_exprs(&[col("commit")], [true], false, true)
+                    .collect()?
+            );
+        }
+
+        // Query: activity of each repository, only when reading more than one
+        if result.iter().any(|n| n.repo != result[0].repo) {
+            query!(
+                "commit_by_repo",
+                "Commit by repository",
+                preprocess(df.clone(), &args)
+                    .group_by([col("repo")])
+                    .agg([
+                        col("commit").n_unique(),
+                        col("author_name").n_unique().alias("authors"),
+                        col("added").sum(),
+                        col("deleted").sum(),
+                        col("commit_date").max().alias("last_commit"),
+                    ])
+                    .sort_by_exprs(&[col("commit")], [true], false, true)
+                    .collect()?
+            );
+        }
+
+        // Query: pull requests merged per month, only with merge commits of pull requests
+        let pull_requests = filter_commits(pull_requests::pull_requests(&result)?.lazy(), &args)
             .select([
-                col("author_name").n_unique().alias("author_count"),
-                col("commit").n_unique().alias("commit_count"),
-                col("author_name").list().0.alias("authors"),
-                col("extension").list().0.alias("extensions"),
-                col("added").sum(),
-                col("deleted").sum(),
-                col("date").max().alias("last commit"),
+                col("number"),
+                col("owner"),
+                col("branch"),
+                col("merged_by"),
+                col("merged_at"),
+                col("year_month"),
             ])
-            .collect()?
-    );
-
-    // Query: How many lines of code were added per author?
-    query!(
-        "commit_by_author",
-        "Commit by author",
-        preprocess(df.clone(), &args)
-            .group_by([col("author_name")])
-            .agg([col("commit").n_unique()])
-            .sort_by_exprs(&[col("commit")], [true], false, true)
-            .collect()?
-    );
-
-    // Query: Total commits by month
-    query!(
-        "commit_by_month",
-        "Commit by month",
-        preprocess(df.clone(), &args)
-            .group_by([col("year_month")])
-            .agg([col("commit").n_unique()])
-            .sort_by_exprs(&[col("year_month")], [false], false, true)
-            .collect()?
-    );
-
-    // Query: Commit by author by date, convert date to YYYY-MM
-    query!(
-        "commit_by_author_by_month",
-        "Commit by author by month",
-        preprocess(df.clone(), &args)
-            .group_by([col("author_name"), col("year_month")])
-            .agg([col("commit").n_unique()])
-            .sort_by_exprs(
-                &[col("author_name"), col("commit")],
-                [false, true],
-                false,
-                true
-            )
-            .collect()?
-    );
-
-    // Query: Top languages
-    query!(
-        "top_languages",
-        "Top languages",
-        preprocess(df.clone(), &args)
-            .group_by([col("extension").alias("language")])
-            .agg([col("commit").n_unique()])
-            .sort_by_exprs(&[col("commit")], [true], false, true)
-            .limit(5)
-            .collect()?
-    );
-
-    // Query: Top commit by weekday
-    query!(
-        "commit_by_weekday",
-        "Commit by weekday",
-        preprocess(df.clone(), &args)
-            .with_column(col("date").dt().weekday().alias("n"))
-            .with_column(col("date").dt().strftime("%A").alias("weekday"))
-            .group_by([col("n"), col("weekday")])
-            .agg([col("commit").n_unique()])
-            .sort_by_exprs(&[col("n")], [false], false, true)
-            .collect()?
-    );
-
-    match args.output {
+            .collect()?;
+        if pull_requests.height() > 0 {
+            query!(
+                "pull_requests_by_month",
+                "Pull requests by month",
+                pull_requests
+                    .clone()
+                    .lazy()
+                    .group_by([col("year_month")])
+                    .agg([
+                        col("number").count().alias("pull_requests"),
+                        col("owner").n_unique().alias("owners"),
+                    ])
+                    .sort_by_exprs(&[col("year_month")], [false], false, true)
+                    .collect()?
+            );
+
+            query!(
+                "pull_requests",
+                "Pull requests",
+                pull_requests
+                    .lazy()
+                    .sort_by_exprs(&[col("merged_at")], [true], false, true)
+                    .limit(PULL_REQUEST_LIMIT)
+                    .collect()?
+            );
+        }
+
+        // Query: commits per weekday and hour of the day
+        query!(
+            "commit_heatmap",
+            "Commit by weekday and hour",
+            heatmap::compute(preprocess(df.clone(), &args), args.timezone)?
+        );
+    }
+
+    match args.output.unwrap_or_default() {
         cli::Output::None => {
+            use std::io::IsTerminal;
+
             for (k, v) in query {
-                println!("{}: {}\n", heading.get(&k).unwrap_or(&k), v)
+                if k == heatmap::QUERY {
+                    let color =
+                        std::io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+                    println!(
+                        "{}:\n{}",
+                        heading.get(&k).unwrap_or(&k),
+                        heatmap::render_terminal(&v, color)?
+                    );
+                } else {
+                    println!("{}: {}\n", heading.get(&k).unwrap_or(&k), v)
+                }
             }
         }
         cli::Output::Json => {
-            use serde_json::{json, to_value, Value};
-
-            let values = to_value(&query)?;
-            let mut out = json!({});
-
-            // BUG-019: Safe JSON object access with pattern matching
-            if let Some(obj) = values.as_object() {
-                for (k, v) in obj.iter() {
-                    let mut cols = json!({});
-
-                    if let Some(columns) = v["columns"].as_array() {
-                        for col in columns.iter() {
-                            if let Some(key) = col["name"].as_str() {
-                                // BUG-024: Safe array access with bounds checking
-                                let values = match col["values"].as_array() {
-                                    Some(vals) if !vals.is_empty() && vals[0].is_object() => vals
-                                        .iter()
-                                        .map(|v| v["values"].clone())
-                                        .collect::<Vec<Value>>(),
-                                    Some(vals) => vals.to_vec(),
-                                    None => vec![],
-                                };
-
-                                cols[key] = Value::Array(values);
-                            }
-                        }
-                    }
-
-                    out[k] = serde_json::to_value(cols)?;
-                }
-            }
-
+            let out = json::render(&heading, &query, args.json_layout)?;
             println!("{:#}", out);
         }
-        _ => {
-            bail!("Not implemented yet.");
+        cli::Output::Html => {
+            println!("{}", html::render(&heading, &query)?);
+        }
+        cli::Output::Markdown => {
+            print!("{}", markdown::render(&heading, &query)?);
         }
     }
 
     Ok(())
 }
+
+/// Rows of the DataFrame, one per commit, author and file.
+/// Credited to the author, or to each of the co-authors with `co_authors`
+fn to_rows<'a>(
+    result: &'a [Numstat],
+    conventionals: &'a [Option<ConventionalCommit>],
+    releases: &'a Releases,
+    co_authors: bool,
+) -> Vec<Row<'a>> {
+    result
+        .par_iter()
+        .zip(conventionals.par_iter())
+        .flat_map(|(n, conventional)| {
+            let release = releases.release_of(n);
+
+            let authors = if co_authors {
+                n.co_authors.iter().collect::<Vec<_>>()
+            } else {
+                vec![&n.author]
+            };
+
+            authors
+                .into_par_iter()
+                .flat_map(|a| n.stats.par_iter().map(move |f| (a, f)))
+                .map(|(a, f)| {
+                    Row::new(vec![
+                        AnyValue::Utf8(&n.commit),
+                        AnyValue::Datetime(
+                            n.date.timestamp_micros(),
+                            TimeUnit::Microseconds,
+                            &None,
+                        ),
+                        AnyValue::Utf8(&a.name),
+                        AnyValue::Utf8(&a.email),
+                        AnyValue::Utf8(&f.new_path),
+                        AnyValue::Utf8(&f.extension),
+                        AnyValue::UInt32(f.added),
+                        AnyValue::UInt32(f.deleted),
+                        AnyValue::Boolean(f.is_binary),
+                        AnyValue::Utf8(&f.old_path),
+                        AnyValue::Utf8(&n.committer.name),
+                        AnyValue::Utf8(&n.committer.email),
+                        AnyValue::Datetime(
+                            n.commit_date.timestamp_micros(),
+                            TimeUnit::Microseconds,
+                            &None,
+                        ),
+                        AnyValue::Int32(n.date.offset().local_minus_utc()),
+                        AnyValue::Int32(n.commit_date.offset().local_minus_utc()),
+                        AnyValue::Utf8(conventional.as_ref().map_or("", |c| c.kind.as_str())),
+                        AnyValue::Utf8(
+                            conventional
+                                .as_ref()
+                                .and_then(|c| c.scope.as_deref())
+                                .unwrap_or_default(),
+                        ),
+                        AnyValue::Boolean(conventional.as_ref().is_some_and(|c| c.breaking)),
+                        AnyValue::Utf8(release),
+                        AnyValue::Utf8(branch_name(&n.source)),
+                        AnyValue::Utf8(&n.repo),
+                    ])
+                })
+                .collect::<Vec<Row>>()
+        })
+        .collect()
+}
+
+/// Built from the schema, so that an empty history still gives empty tables
+fn to_dataframe(rows: &[Row]) -> Result<DataFrame> {
+    let datetime = DataType::Datetime(TimeUnit::Microseconds, None);
+    let schema = Schema::from_iter([
+        Field::new("commit", DataType::Utf8),
+        Field::new("date", datetime.clone()),
+        Field::new("author_name", DataType::Utf8),
+        Field::new("author_email", DataType::Utf8),
+        Field::new("path", DataType::Utf8),
+        Field::new("extension", DataType::Utf8),
+        Field::new("added", DataType::UInt32),
+        Field::new("deleted", DataType::UInt32),
+        Field::new("is_binary", DataType::Boolean),
+        Field::new("old_path", DataType::Utf8),
+        Field::new("committer_name", DataType::Utf8),
+        Field::new("committer_email", DataType::Utf8),
+        Field::new("commit_date", datetime),
+        Field::new("tz_offset", DataType::Int32),
+        Field::new("commit_tz_offset", DataType::Int32),
+        Field::new("commit_type", DataType::Utf8),
+        Field::new("commit_scope", DataType::Utf8),
+        Field::new("breaking", DataType::Boolean),
+        Field::new("release", DataType::Utf8),
+        Field::new("branch", DataType::Utf8),
+        Field::new("repo", DataType::Utf8),
+    ]);
+
+    Ok(DataFrame::from_rows_and_schema(rows, &schema)?)
+}
+
+/// Short name of the ref a commit was reached from, e.g. `main` for `refs/heads/main`
+fn branch_name(source: &str) -> &str {
+    ["refs/heads/", "refs/remotes/", "refs/tags/", "refs/"]
+        .iter()
+        .find_map(|prefix| source.strip_prefix(prefix))
+        .unwrap_or(source)
+}
+
+/// Lines added, deleted and net, signed so the net can be negative
+fn lines() -> [Expr; 3] {
+    [
+        col("added").cast(DataType::Int64).sum().alias("added"),
+        col("deleted").cast(DataType::Int64).sum().alias("deleted"),
+        (col("added").cast(DataType::Int64) - col("deleted").cast(DataType::Int64))
+            .sum()
+            .alias("net"),
+    ]
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use polars::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use crate::format::{cell, escape_markdown as escape};
+
+/// Render every query as GitHub-flavoured markdown tables, e.g. for PR comments
+pub fn render(heading: &HashMap<&str, &str>, query: &BTreeMap<&str, DataFrame>) -> Result<String> {
+    let mut out = String::new();
+
+    writeln!(out, "# Git Insights")?;
+
+    for (k, df) in query {
+        let title = heading.get(k).unwrap_or(k);
+
+        writeln!(out)?;
+        writeln!(out, "## {}", escape(title))?;
+        writeln!(out)?;
+        out.push_str(&table(df)?);
+    }
+
+    Ok(out)
+}
+
+fn table(df: &DataFrame) -> Result<String> {
+    let mut out = String::new();
+
+    let names = df
+        .get_column_names()
+        .iter()
+        .map(|name| escape(name))
+        .collect::<Vec<_>>();
+    writeln!(out, "| {} |", names.join(" | "))?;
+
+    // Right-align the numbers
+    let aligns = df
+        .get_columns()
+        .iter()
+        .map(|s| {
+            if s.dtype().is_numeric() {
+                "---:"
+            } else {
+                "---"
+            }
+        })
+        .collect::<Vec<_>>();
+    writeln!(out, "| {} |", aligns.join(" | "))?;
+
+    for i in 0..df.height() {
+        let cells = df
+            .get_columns()
+            .iter()
+            .map(|s| cell(s, i).map(|c| escape(&c)))
+            .collect::<Result<Vec<_>>>()?;
+        writeln!(out, "| {} |", cells.join(" | "))?;
+    }
+
+    Ok(out)
+}
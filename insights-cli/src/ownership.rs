@@ -0,0 +1,135 @@
+use anyhow::Result;
+use polars::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Authors of a directory, by their share of the churn
+pub struct Ownership {
+    pub directory: String,
+    pub churn: f64,
+    /// (name, email, churn), the largest first
+    pub authors: Vec<(String, String, f64)>,
+    /// Minimum number of authors covering the threshold of the churn
+    pub bus_factor: usize,
+}
+
+/// Ownership of each directory, `threshold` is the share of the churn
+/// to cover by the bus factor authors, e.g. 0.8
+pub fn compute(df: LazyFrame, threshold: f64) -> Result<Vec<Ownership>> {
+    let df = df
+        .group_by([col("directory"), col("author_name"), col("author_email")])
+        .agg([(col("added") + col("deleted")).sum().alias("churn")])
+        .collect()?;
+
+    let directories = df.column("directory")?.utf8()?;
+    let names = df.column("author_name")?.utf8()?;
+    let emails = df.column("author_email")?.utf8()?;
+    let churns = df.column("churn")?.cast(&DataType::Float64)?;
+    let churns = churns.f64()?;
+
+    let mut by_directory: BTreeMap<String, Vec<(String, String, f64)>> = BTreeMap::new();
+    for i in 0..df.height() {
+        by_directory
+            .entry(directories.get(i).unwrap_or(".").to_string())
+            .or_default()
+            .push((
+                names.get(i).unwrap_or_default().to_string(),
+                emails.get(i).unwrap_or_default().to_string(),
+                churns.get(i).unwrap_or_default(),
+            ));
+    }
+
+    let ownership = by_directory
+        .into_iter()
+        .map(|(directory, mut authors)| {
+            authors.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+            let churn = authors.iter().map(|a| a.2).sum::<f64>();
+
+            // Count the authors until their cumulated churn covers the threshold
+            let mut covered = 0.0;
+            let bus_factor = authors
+                .iter()
+                .take_while(|a| {
+                    let before = covered;
+                    covered += a.2;
+                    before < threshold * churn
+                })
+                .count()
+                .max(1);
+
+            Ownership {
+                directory,
+                churn,
+                authors,
+                bus_factor,
+            }
+        })
+        .collect();
+
+    Ok(ownership)
+}
+
+/// One row per directory, the ones with a bus factor of 1 first
+pub fn to_dataframe(ownership: &[Ownership]) -> Result<DataFrame> {
+    let share = |o: &Ownership| match o.churn {
+        churn if churn > 0.0 => (o.authors[0].2 / churn * 100.0).round(),
+        _ => 100.0,
+    };
+
+    let df = df!(
+        "directory" => ownership.iter().map(|o| o.directory.as_str()).collect::<Vec<_>>(),
+        "owner" => ownership.iter().map(|o| o.authors[0].0.as_str()).collect::<Vec<_>>(),
+        "owner_share" => ownership.iter().map(share).collect::<Vec<_>>(),
+        "authors" => ownership.iter().map(|o| o.authors.len() as u32).collect::<Vec<_>>(),
+        "churn" => ownership.iter().map(|o| o.churn as u64).collect::<Vec<_>>(),
+        "bus_factor" => ownership.iter().map(|o| o.bus_factor as u32).collect::<Vec<_>>(),
+        "at_risk" => ownership.iter().map(|o| o.bus_factor == 1).collect::<Vec<_>>(),
+    )?;
+
+    Ok(df
+        .lazy()
+        .sort_by_exprs(
+            &[col("bus_factor"), col("churn")],
+            [false, true],
+            false,
+            true,
+        )
+        .collect()?)
+}
+
+/// Draft CODEOWNERS, the bus factor authors of each directory own it
+pub fn codeowners(ownership: &[Ownership]) -> Result<String> {
+    let mut out = String::new();
+
+    // GitHub ignores the emails of the users without write access, without a warning
+    writeln!(
+        out,
+        "# DRAFT generated by girs from the git history, review before use."
+    )?;
+    writeln!(
+        out,
+        "# The owners are author emails, GitHub only honors those of users with write access:"
+    )?;
+    writeln!(out, "# replace them with @username or @org/team-name.")?;
+
+    // The root first, the last matching pattern takes precedence
+    let mut ownership = ownership.iter().collect::<Vec<_>>();
+    ownership.sort_by_key(|o| (o.directory != ".", o.directory.as_str()));
+
+    for o in ownership {
+        let pattern = match o.directory.as_str() {
+            "." => "*".to_string(),
+            dir => format!("/{}/", dir),
+        };
+        let owners = o
+            .authors
+            .iter()
+            .take(o.bus_factor)
+            .map(|a| a.1.as_str())
+            .collect::<Vec<_>>();
+
+        writeln!(out, "{} {}", pattern, owners.join(" "))?;
+    }
+
+    Ok(out)
+}
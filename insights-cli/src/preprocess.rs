@@ -1,64 +1,38 @@
 use polars::prelude::*;
 
-use crate::cli::Cli;
+use crate::cli::{Cli, DateField};
+use crate::teams;
 
 const DEFAULT_REMAP_EXT: [&str; 4] = ["tsx=>ts", "jsx=>js", "htm=>html", "yml=>yaml"];
 const DEFAULT_IGNORE_EXT: [&str; 4] = ["lock", "staging", "local", "license"];
 
 pub fn preprocess(df: DataFrame, args: &Cli) -> LazyFrame {
-    let df = df
-        .lazy()
-        .with_column(col("date").dt().strftime("%Y-%m").alias("year_month"));
+    let df = filter_commits(df.lazy(), args);
+
+    let df = df.with_column(col("date").dt().strftime("%Y-%m").alias("year_month"));
 
     // Drop duplicates
     let df = df.unique_stable(None, UniqueKeepStrategy::Last);
 
-    // Filter by year
-    let df = if !args.year.is_empty() {
-        df.filter(
-            col("date")
-                .dt()
-                .year()
-                .is_in(lit(Series::from_iter(args.year.clone()))),
-        )
-    } else {
-        df
-    };
-
-    // Filter by authors
-    let df = if !args.author.is_empty() {
-        df.filter(col("author_name").is_in(lit(Series::from_iter(args.author.clone()))))
-    } else {
-        df
-    };
-
-    // Filter by ignore authors
-    let df = if !args.ignore_author.is_empty() {
-        df.filter(
-            col("author_name")
-                .is_in(lit(Series::from_iter(args.ignore_author.clone())))
-                .not(),
-        )
-    } else {
-        df
-    };
-
-    // Normalize extensions
+    // Normalize extensions, the defaults can be replaced by the config
+    let ignore_exts = args
+        .default_ignore_ext
+        .clone()
+        .unwrap_or_else(|| DEFAULT_IGNORE_EXT.map(String::from).to_vec());
     let df = df
         .with_column(col("extension").str().to_lowercase().alias("extension"))
         .filter(
             col("extension")
-                .is_in(lit(Series::from_iter(DEFAULT_IGNORE_EXT)))
+                .is_in(lit(Series::from_iter(ignore_exts)))
                 .not(),
         );
 
     // Remap extensions using default
-    // Convert array to slice
-    let exts = &DEFAULT_REMAP_EXT
-        .iter()
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
-    let df = modify_column(df, "extension", exts);
+    let exts = args
+        .default_remap_ext
+        .clone()
+        .unwrap_or_else(|| DEFAULT_REMAP_EXT.map(String::from).to_vec());
+    let df = modify_column(df, "extension", &exts);
 
     // Ignore extensions
     let df = if !args.ignore_ext.is_empty() {
@@ -69,6 +43,25 @@ pub fn preprocess(df: DataFrame, args: &Cli) -> LazyFrame {
         df
     };
 
+    // Ignore binary files
+    let df = if args.ignore_binary {
+        df.filter(col("is_binary").not())
+    } else {
+        df
+    };
+
+    // Ignore the outlier commits, e.g. vendoring or generated code
+    let df = match args.max_commit_lines {
+        Some(max) => df.filter(
+            (col("added") + col("deleted"))
+                .cast(DataType::UInt64)
+                .sum()
+                .over([col("commit"), col("author_name")])
+                .lt_eq(lit(max)),
+        ),
+        None => df,
+    };
+
     // Remap the author name
     let df = if !args.remap_name.is_empty() {
         modify_column(df, "author_name", &args.remap_name)
@@ -91,10 +84,90 @@ pub fn preprocess(df: DataFrame, args: &Cli) -> LazyFrame {
         df
     };
 
+    // Directory of the path, up to --depth levels, "." for the files at the root
+    let df = df.with_column(
+        col("path")
+            .str()
+            .extract(
+                lit(format!(r"^([^/]+(?:/[^/]+){{0,{}}})/", args.depth - 1)),
+                1,
+            )
+            .fill_null(lit("."))
+            .alias("directory"),
+    );
+
+    // Team of the author, after the remaps
+    let df = df.with_column(teams::team(&args.teams));
+
     // Should cache the preprocessed to prevent reprocessing
     df.cache()
 }
 
+/// Commit filters of the rows, also applied to the pull requests: the `--date` field,
+/// `--year`, `--since`, `--until`, `--author` and `--ignore-author`
+pub fn filter_commits(df: LazyFrame, args: &Cli) -> LazyFrame {
+    // Use the commit date for time-based queries
+    let df = if args.date == DateField::Commit {
+        df.with_columns([
+            col("commit_date").alias("date"),
+            col("commit_tz_offset").alias("tz_offset"),
+        ])
+    } else {
+        df
+    };
+
+    // Filter by year
+    let df = if !args.year.is_empty() {
+        df.filter(
+            col("date")
+                .dt()
+                .year()
+                .is_in(lit(Series::from_iter(args.year.clone()))),
+        )
+    } else {
+        df
+    };
+
+    // Filter by date range, git already did it for the git repositories
+    // but only --since with the author date, and not for the numstat files
+    let df = match args.since {
+        Some(since) => df.filter(
+            col("date")
+                .dt()
+                .timestamp(TimeUnit::Microseconds)
+                .gt_eq(lit(since.timestamp_micros())),
+        ),
+        None => df,
+    };
+    let df = match args.until {
+        Some(until) => df.filter(
+            col("date")
+                .dt()
+                .timestamp(TimeUnit::Microseconds)
+                .lt_eq(lit(until.timestamp_micros())),
+        ),
+        None => df,
+    };
+
+    // Filter by authors
+    let df = if !args.author.is_empty() {
+        df.filter(col("author_name").is_in(lit(Series::from_iter(args.author.clone()))))
+    } else {
+        df
+    };
+
+    // Filter by ignore authors
+    if !args.ignore_author.is_empty() {
+        df.filter(
+            col("author_name")
+                .is_in(lit(Series::from_iter(args.ignore_author.clone())))
+                .not(),
+        )
+    } else {
+        df
+    }
+}
+
 fn modify_column(df: LazyFrame, col_name: &str, from_to: &[String]) -> LazyFrame {
     // Replace the value of the author_name column
     // Replace the value [from]=>[to] or [to]<=[from]
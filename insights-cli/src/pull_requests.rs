@@ -0,0 +1,71 @@
+use anyhow::Result;
+use lazy_static::lazy_static;
+use numstat_parser::Numstat;
+use polars::prelude::*;
+use regex::Regex;
+
+lazy_static! {
+    static ref MERGE_PR_RE: Regex =
+        Regex::new(r"^Merge pull request #(?P<number>\d+) from (?P<source>\S+)").unwrap();
+}
+
+/// Pull requests merged with a `Merge pull request #N from owner/branch` commit,
+/// one row per pull request: number, owner and branch of the source, merged by and at.
+/// The dates and author of the merge commit are kept to filter them as the rows
+pub fn pull_requests(numstats: &[Numstat]) -> Result<DataFrame> {
+    let mut numbers = vec![];
+    let mut owners = vec![];
+    let mut branches = vec![];
+    let mut merged_by = vec![];
+    let mut merged_at = vec![];
+    let mut months = vec![];
+    let mut authors = vec![];
+    let mut dates = vec![];
+    let mut tz_offsets = vec![];
+    let mut commit_tz_offsets = vec![];
+
+    for n in numstats.iter().filter(|n| !n.merges.is_empty()) {
+        let captures = match n
+            .message
+            .lines()
+            .next()
+            .and_then(|l| MERGE_PR_RE.captures(l))
+        {
+            Some(captures) => captures,
+            None => continue,
+        };
+
+        // Source is `owner/branch`, the branch can have slashes
+        let source = &captures["source"];
+        let (owner, branch) = source.split_once('/').unwrap_or(("", source));
+
+        numbers.push(captures["number"].parse::<u32>()?);
+        owners.push(owner.to_string());
+        branches.push(branch.to_string());
+        merged_by.push(n.committer.name.clone());
+        merged_at.push(n.commit_date.timestamp_micros());
+        months.push(n.commit_date.format("%Y-%m").to_string());
+        authors.push(n.author.name.clone());
+        dates.push(n.date.timestamp_micros());
+        tz_offsets.push(n.date.offset().local_minus_utc());
+        commit_tz_offsets.push(n.commit_date.offset().local_minus_utc());
+    }
+
+    let datetime = DataType::Datetime(TimeUnit::Microseconds, None);
+    let merged_at = Series::new("merged_at", merged_at).cast(&datetime)?;
+    let commit_date = merged_at.clone().with_name("commit_date");
+
+    Ok(DataFrame::new(vec![
+        Series::new("number", numbers),
+        Series::new("owner", owners),
+        Series::new("branch", branches),
+        Series::new("merged_by", merged_by),
+        merged_at,
+        Series::new("year_month", months),
+        Series::new("author_name", authors),
+        Series::new("date", dates).cast(&datetime)?,
+        commit_date,
+        Series::new("tz_offset", tz_offsets),
+        Series::new("commit_tz_offset", commit_tz_offsets),
+    ])?)
+}
@@ -0,0 +1,209 @@
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset};
+use numstat_parser::{Merges, Numstat};
+use polars::prelude::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Commits after the last tag
+pub const UNRELEASED: &str = "(unreleased)";
+
+/// Number of languages in the release report
+const TOP_LANGUAGES: usize = 3;
+
+/// Release of each commit, the first tag containing it
+pub struct Releases {
+    /// Tagged commits, the oldest first
+    tags: Vec<(DateTime<FixedOffset>, String)>,
+    /// Release of the commits reached from a tag through their parents
+    by_commit: HashMap<String, String>,
+    /// Repositories read with the parents of the commits
+    ancestry: HashSet<String>,
+}
+
+impl Releases {
+    /// The first tag of each tagged commit, ordered by the commit date.
+    /// The commits are in the release of the oldest tag reaching them through their parents,
+    /// unless the history is incomplete: without the parents (the numstat files) or without
+    /// the merge commits, the release is the first tag on or after the commit date instead
+    pub fn new(numstats: &[Numstat], merges: Merges) -> Self {
+        let mut tagged = numstats
+            .iter()
+            .filter_map(|n| n.tags.first().map(|tag| (n.commit_date, tag.clone(), n)))
+            .collect::<Vec<_>>();
+        tagged.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+        tagged.dedup_by(|a, b| a.1 == b.1);
+
+        let ancestry = if merges == Merges::Include {
+            numstats
+                .iter()
+                .filter(|n| !n.parents.is_empty())
+                .map(|n| n.repo.clone())
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
+        // From the oldest tag, stop at the commits already in an older release
+        let by_hash = numstats
+            .iter()
+            .map(|n| (n.commit.as_str(), n))
+            .collect::<HashMap<_, _>>();
+        let mut by_commit = HashMap::new();
+        for (_, tag, n) in tagged.iter().filter(|(_, _, n)| ancestry.contains(&n.repo)) {
+            let mut stack = vec![n.commit.as_str()];
+            while let Some(commit) = stack.pop() {
+                if by_commit.contains_key(commit) {
+                    continue;
+                }
+                if let Some(n) = by_hash.get(commit) {
+                    by_commit.insert(commit.to_string(), tag.clone());
+                    stack.extend(n.parents.iter().map(String::as_str));
+                }
+            }
+        }
+
+        Self {
+            tags: tagged
+                .into_iter()
+                .map(|(date, tag, _)| (date, tag))
+                .collect(),
+            by_commit,
+            ancestry,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// First release containing the commit, see [`Releases::new`]
+    pub fn release_of(&self, numstat: &Numstat) -> &str {
+        if self.ancestry.contains(&numstat.repo) {
+            return self
+                .by_commit
+                .get(&numstat.commit)
+                .map_or(UNRELEASED, String::as_str);
+        }
+
+        let idx = self
+            .tags
+            .partition_point(|(date, _)| *date < numstat.commit_date);
+
+        self.tags
+            .get(idx)
+            .map(|(_, tag)| tag.as_str())
+            .unwrap_or(UNRELEASED)
+    }
+}
+
+#[derive(Default)]
+struct Interval<'a> {
+    commits: HashSet<&'a str>,
+    contributors: HashSet<&'a str>,
+    new_contributors: BTreeSet<&'a str>,
+    added: u64,
+    deleted: u64,
+    languages: HashMap<&'a str, u64>,
+    last_commit: i64,
+}
+
+/// One row per release interval, the latest first: commits, contributors,
+/// lines, top languages and the contributors with their first commit in it
+pub fn report(df: LazyFrame) -> Result<DataFrame> {
+    let df = df
+        .select([
+            col("release"),
+            col("commit"),
+            col("commit_date"),
+            col("author_name"),
+            col("extension"),
+            col("added"),
+            col("deleted"),
+        ])
+        .collect()?;
+
+    let releases = df.column("release")?.utf8()?;
+    let commits = df.column("commit")?.utf8()?;
+    let dates = df.column("commit_date")?.cast(&DataType::Int64)?;
+    let dates = dates.i64()?;
+    let authors = df.column("author_name")?.utf8()?;
+    let extensions = df.column("extension")?.utf8()?;
+    let added = df.column("added")?.cast(&DataType::UInt64)?;
+    let added = added.u64()?;
+    let deleted = df.column("deleted")?.cast(&DataType::UInt64)?;
+    let deleted = deleted.u64()?;
+
+    let mut intervals: HashMap<&str, Interval> = HashMap::new();
+    let mut first_commits: HashMap<&str, (i64, &str)> = HashMap::new();
+
+    for i in 0..df.height() {
+        let release = releases.get(i).unwrap_or(UNRELEASED);
+        let author = authors.get(i).unwrap_or_default();
+        let date = dates.get(i).unwrap_or_default();
+        let lines = added.get(i).unwrap_or(0) + deleted.get(i).unwrap_or(0);
+
+        let interval = intervals.entry(release).or_default();
+        interval.commits.insert(commits.get(i).unwrap_or_default());
+        interval.contributors.insert(author);
+        interval.added += added.get(i).unwrap_or(0);
+        interval.deleted += deleted.get(i).unwrap_or(0);
+        *interval
+            .languages
+            .entry(extensions.get(i).unwrap_or_default())
+            .or_default() += lines;
+        interval.last_commit = interval.last_commit.max(date);
+
+        let first = first_commits.entry(author).or_insert((date, release));
+        if date < first.0 {
+            *first = (date, release);
+        }
+    }
+
+    for (author, (_, release)) in first_commits {
+        if let Some(interval) = intervals.get_mut(release) {
+            interval.new_contributors.insert(author);
+        }
+    }
+
+    let mut intervals = intervals.into_iter().collect::<Vec<_>>();
+    intervals.sort_by_key(|(_, interval)| std::cmp::Reverse(interval.last_commit));
+
+    let top_languages = |interval: &Interval| {
+        let mut languages = interval
+            .languages
+            .iter()
+            .filter(|(lang, _)| !lang.is_empty())
+            .collect::<Vec<_>>();
+        languages.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        languages
+            .iter()
+            .take(TOP_LANGUAGES)
+            .map(|(lang, _)| **lang)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let df = df!(
+        "release" => intervals.iter().map(|(r, _)| *r).collect::<Vec<_>>(),
+        "date" => intervals
+            .iter()
+            .map(|(_, i)| {
+                DateTime::from_timestamp_micros(i.last_commit)
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>(),
+        "commits" => intervals.iter().map(|(_, i)| i.commits.len() as u32).collect::<Vec<_>>(),
+        "contributors" => intervals.iter().map(|(_, i)| i.contributors.len() as u32).collect::<Vec<_>>(),
+        "added" => intervals.iter().map(|(_, i)| i.added).collect::<Vec<_>>(),
+        "deleted" => intervals.iter().map(|(_, i)| i.deleted).collect::<Vec<_>>(),
+        "top_languages" => intervals.iter().map(|(_, i)| top_languages(i)).collect::<Vec<_>>(),
+        "new_contributors" => intervals
+            .iter()
+            .map(|(_, i)| i.new_contributors.iter().copied().collect::<Vec<_>>().join(", "))
+            .collect::<Vec<_>>(),
+    )?;
+
+    Ok(df)
+}
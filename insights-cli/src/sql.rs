@@ -0,0 +1,28 @@
+use anyhow::{Context, Result};
+use polars::prelude::*;
+use polars::sql::SQLContext;
+
+/// Run the SQL over the preprocessed rows, registered as the `commits` table,
+/// and the `commits_summary` view with one row per commit
+pub fn execute(commits: LazyFrame, sql: &str) -> Result<DataFrame> {
+    let commits_summary = commits.clone().group_by_stable([col("commit")]).agg([
+        col("date").first(),
+        col("year_month").first(),
+        col("author_name").first(),
+        col("author_email").first(),
+        col("committer_name").first(),
+        col("committer_email").first(),
+        col("commit_date").first(),
+        col("path").count().alias("files"),
+        col("added").sum(),
+        col("deleted").sum(),
+    ]);
+
+    let mut ctx = SQLContext::new();
+    ctx.register("commits", commits);
+    ctx.register("commits_summary", commits_summary);
+
+    ctx.execute(sql)
+        .and_then(|df| df.collect())
+        .with_context(|| format!("Running query `{}`", sql))
+}
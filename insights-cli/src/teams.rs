@@ -0,0 +1,98 @@
+use anyhow::{Context, Result};
+use polars::prelude::*;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use std::fmt;
+use std::path::Path;
+
+/// Authors not matching any team
+pub const NO_TEAM: &str = "(no team)";
+
+/// Team name to the email or name patterns of its members, in the order of the files
+#[derive(Debug, Default)]
+pub struct Teams(Vec<(String, Vec<String>)>);
+
+impl Teams {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add the patterns of the teams, after the patterns of the teams already known
+    pub fn extend(&mut self, teams: Teams) {
+        for (team, patterns) in teams.0 {
+            match self.0.iter_mut().find(|(name, _)| *name == team) {
+                Some((_, existing)) => existing.extend(patterns),
+                None => self.0.push((team, patterns)),
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Teams {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TeamsVisitor;
+
+        impl<'de> Visitor<'de> for TeamsVisitor {
+            type Value = Teams;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a table of team name to the member patterns")
+            }
+
+            // Keep the order of the keys, the first team matching wins
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Teams, A::Error> {
+                let mut teams = Teams::default();
+                while let Some((team, patterns)) = map.next_entry()? {
+                    teams.extend(Teams(vec![(team, patterns)]));
+                }
+
+                Ok(teams)
+            }
+        }
+
+        deserializer.deserialize_map(TeamsVisitor)
+    }
+}
+
+/// Team membership file, team name to the email or name patterns of its members:
+///
+/// ```toml
+/// Platform = ["me@duyet.net", "*@infra.example.com"]
+/// Data = ["Duyet*"]
+/// ```
+pub fn from_file(path: &Path) -> Result<Teams> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Reading teams `{}`", path.display()))?;
+
+    toml::from_str(&content).with_context(|| format!("Parsing teams `{}`", path.display()))
+}
+
+/// The `team` column, the first team in the files with a pattern matching
+/// the author email or name wins
+pub fn team(teams: &Teams) -> Expr {
+    let mut expr = lit(NO_TEAM);
+
+    // Build from the last pattern so the first one is checked first
+    for (name, patterns) in teams.0.iter().rev() {
+        for pattern in patterns.iter().rev() {
+            let re = to_regex(pattern);
+
+            expr = when(
+                col("author_email")
+                    .str()
+                    .contains(lit(re.clone()), true)
+                    .or(col("author_name").str().contains(lit(re), true)),
+            )
+            .then(lit(name.clone()))
+            .otherwise(expr);
+        }
+    }
+
+    expr.alias("team")
+}
+
+/// Case-insensitive match of the whole value, `*` matches anything
+fn to_regex(pattern: &str) -> String {
+    let parts = pattern.split('*').map(regex::escape).collect::<Vec<_>>();
+
+    format!("(?i)^{}$", parts.join(".*"))
+}
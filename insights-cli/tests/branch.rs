@@ -0,0 +1,80 @@
+mod common;
+
+use common::git;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn commit(dir: &Path, file: &str) {
+    std::fs::write(dir.join(file), file).unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-m", file]);
+}
+
+fn repo() -> tempfile::TempDir {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+
+    git(dir, &["init", "-b", "main"]);
+    commit(dir, "a.rs");
+    commit(dir, "b.rs");
+    git(dir, &["checkout", "-b", "feature"]);
+    commit(dir, "c.rs");
+    commit(dir, "d.rs");
+    git(dir, &["checkout", "main"]);
+
+    temp_dir
+}
+
+#[test]
+fn commit_by_branch() {
+    let temp_dir = repo();
+    let json = common::json(temp_dir.path(), &[]);
+
+    let rows = json["queries"]["commit_by_branch"]["rows"]
+        .as_array()
+        .unwrap();
+    let commits = |branch: &str| {
+        rows.iter()
+            .find(|r| r["branch"] == branch)
+            .map(|r| r["commit"].clone())
+    };
+    assert_eq!(rows.len(), 2);
+    assert_eq!(commits("main"), Some(2.into()));
+    assert_eq!(commits("feature"), Some(2.into()));
+}
+
+#[test]
+fn only_branch() {
+    let temp_dir = repo();
+
+    // $ insights <git dir> --branch main
+    let json = common::json(temp_dir.path(), &["--branch", "main"]);
+    assert_eq!(json["queries"]["summary"]["rows"][0]["commit_count"], 2);
+}
+
+#[test]
+fn only_rev_range() {
+    let temp_dir = repo();
+
+    // $ insights <git dir> --rev-range main..feature
+    let json = common::json(temp_dir.path(), &["--rev-range", "main..feature"]);
+    assert_eq!(json["queries"]["summary"]["rows"][0]["commit_count"], 2);
+}
+
+#[test]
+fn no_branch_in_numstat_file() {
+    let (_dir, path) = common::numstat_file(
+        "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 1 Jun 2022 10:00:00 +0700
+
+    feat: first
+
+1\t0\tsrc/main.rs
+",
+    );
+
+    // Without the source of the commits, there is no branch to group by
+    let json = common::json(&path, &[]);
+    assert!(json["queries"]["commit_by_branch"].is_null());
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: pair programming
+
+    Co-authored-by: Pair Partner <pair@duyet.net>
+
+1	0	src/main.rs
+";
+
+#[test]
+fn parse_with_co_authors() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // Without --co-authors: only the author is credited
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Duyet Le"))
+        .stdout(predicates::str::contains("Pair Partner").count(0));
+
+    // $ insights numstat.txt --co-authors
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--co-authors")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Duyet Le"))
+        .stdout(predicates::str::contains("Pair Partner"));
+}
+
+#[test]
+fn co_authors_do_not_multiply_lines() {
+    // $ insights numstat.txt --co-authors --output json
+    let json = common::json_of(NUMSTAT, &["--co-authors"]);
+
+    // Both are credited with the commit and its line
+    let rows = json["queries"]["lines_by_author"]["rows"]
+        .as_array()
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|r| r["added"] == 1));
+
+    // But the line is only counted once in the totals
+    assert_eq!(json["queries"]["summary"]["rows"][0]["added"], 1);
+    assert_eq!(json["queries"]["lines_by_month"]["rows"][0]["added"], 1);
+}
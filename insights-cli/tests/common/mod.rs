@@ -0,0 +1,52 @@
+//! Helpers shared by the integration tests, the fixtures stay in each test file
+#![allow(dead_code)]
+
+use assert_cmd::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Temporary `numstat.txt` with the content, removed with the returned dir
+pub fn numstat_file(numstat: &str) -> (TempDir, PathBuf) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("numstat.txt");
+    std::fs::write(&path, numstat).unwrap();
+
+    (temp_dir, path)
+}
+
+/// `girs <path> --output json <args>`, the path is a numstat file or a git repository
+pub fn json(path: &Path, args: &[&str]) -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    let output = cmd
+        .arg(path)
+        .args(["--output", "json"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// `girs numstat.txt --output json <args>` with the numstat content
+pub fn json_of(numstat: &str, args: &[&str]) -> serde_json::Value {
+    let (_temp_dir, path) = numstat_file(numstat);
+    json(&path, args)
+}
+
+/// `git <args>` in `dir` with a fixed identity
+pub fn git(dir: &Path, args: &[&str]) {
+    git_with_env(dir, args, &[]);
+}
+
+/// Same as [`git`] with the environment variables, e.g. `GIT_COMMITTER_DATE`
+pub fn git_with_env(dir: &Path, args: &[&str], envs: &[(&str, &str)]) {
+    Command::new("git")
+        .args(["-c", "user.name=Duyet Le", "-c", "user.email=me@duyet.net"])
+        .args(args)
+        .envs(envs.iter().copied())
+        .current_dir(dir)
+        .assert()
+        .success();
+}
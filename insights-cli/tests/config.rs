@@ -0,0 +1,115 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: duyet <lvduit08@gmail.com>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+2	1	README.md
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: dependabot[bot] <bot@github.com>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    chore: bump
+
+5	3	Cargo.toml
+";
+
+const CONFIG: &str = r#"
+ignore_author = ["dependabot[bot]"]
+remap_name = ["duyet=>Duyet Le"]
+output = "markdown"
+"#;
+
+#[test]
+fn parse_with_config_file() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let config = temp_dir.path().join("girs.toml");
+    std::fs::write(&config, CONFIG).unwrap();
+
+    // $ insights numstat.txt --config girs.toml
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--config")
+        .arg(&config)
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("# Git Insights"))
+        .stdout(predicates::str::contains("Duyet Le"))
+        .stdout(predicates::str::contains("dependabot").count(0));
+}
+
+#[test]
+fn parse_with_discovered_config_and_flags() {
+    let (temp_dir, _path) = common::numstat_file(NUMSTAT);
+    std::fs::write(temp_dir.path().join(".girs.toml"), CONFIG).unwrap();
+
+    // $ insights numstat.txt --output json
+    // The .girs.toml in the current directory is used, the flags take precedence
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .arg("numstat.txt")
+        .arg("--output")
+        .arg("json")
+        .arg("--ignore-ext")
+        .arg("md")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let summary = &json["queries"]["summary"]["rows"][0];
+    assert_eq!(summary["authors"], serde_json::json!(["Duyet Le"]));
+    assert_eq!(summary["extensions"], serde_json::json!(["rs"]));
+}
+
+#[test]
+fn parse_with_invalid_config() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let config = temp_dir.path().join("girs.toml");
+    std::fs::write(&config, "unknown_key = 1\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--config")
+        .arg(&config)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Parsing config"));
+}
+
+#[test]
+fn flags_turn_off_the_config() {
+    let (temp_dir, path) = common::numstat_file(
+        "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: logo
+
+1	0	src/main.rs
+-	-	logo.png
+",
+    );
+    let config = temp_dir.path().join("girs.toml");
+    std::fs::write(&config, "ignore_binary = true\n").unwrap();
+
+    let run = |flags: &[&str]| {
+        let args = [&["--config", config.to_str().unwrap()][..], flags].concat();
+        let json = common::json(&path, &args);
+        json["queries"]["summary"]["rows"][0]["extensions"].clone()
+    };
+
+    assert_eq!(run(&[]), serde_json::json!(["rs"]));
+    assert_eq!(
+        run(&["--no-ignore-binary"]),
+        serde_json::json!(["rs", "png"])
+    );
+}
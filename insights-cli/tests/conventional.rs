@@ -0,0 +1,83 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 920ef0b0ecb95ff653e9d6a4863b64662bb82834 (HEAD -> main)
+Author: Duyet Le <me@duyet.net>
+Date:   Fri, 3 Mar 2023 10:00:00 +0700
+
+    fix(cli)!: rename --out to --output
+
+2	2	src/cli.rs
+
+commit 598070021341c247fa041baac291fa1bfa0133b4 (tag: v1.0.0)
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat(parser): parse tags
+
+    BREAKING CHANGE: Numstat::tags is a Vec
+
+5	3	src/lib.rs
+
+commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+
+commit 4d07e012d8a31d7a19f4c3461d11e0ad83868d6d
+Author: Duyet Le <me@duyet.net>
+Date:   Mon, 9 Jan 2023 00:35:39 +0700
+
+    Initial commit
+
+1	0	README.md
+";
+
+#[test]
+fn commit_by_type_by_month() {
+    let json = common::json_of(NUMSTAT, &[]);
+
+    let rows = json["queries"]["commit_by_type_by_month"]["rows"]
+        .as_array()
+        .unwrap();
+    let count = |month: &str, kind: &str| {
+        rows.iter()
+            .find(|r| r["year_month"] == month && r["type"] == kind)
+            .map(|r| r["commit"].clone())
+    };
+
+    assert_eq!(count("2023-01", "feat"), Some(1.into()));
+    assert_eq!(count("2023-01", "(none)"), Some(1.into()));
+    assert_eq!(count("2023-02", "feat"), Some(1.into()));
+    assert_eq!(count("2023-03", "fix"), Some(1.into()));
+}
+
+#[test]
+fn breaking_by_release() {
+    let json = common::json_of(NUMSTAT, &[]);
+
+    let rows = &json["queries"]["breaking_by_release"]["rows"];
+    assert_eq!(rows.as_array().unwrap().len(), 2);
+    assert_eq!(rows[0]["release"], "(unreleased)");
+    assert_eq!(rows[0]["breaking_changes"], 1);
+    assert_eq!(rows[1]["release"], "v1.0.0");
+    assert_eq!(rows[1]["breaking_changes"], 1);
+}
+
+#[test]
+fn query_conventional_columns() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg("query")
+        .arg("SELECT commit_scope FROM commits WHERE breaking AND commit_type = 'fix'")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("cli"));
+}
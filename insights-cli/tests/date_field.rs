@@ -0,0 +1,43 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author:     Duyet Le <me@duyet.net>
+AuthorDate: Sat, 31 Dec 2022 23:00:00 +0700
+Commit:     GitHub <noreply@github.com>
+CommitDate: Mon, 2 Jan 2023 08:00:00 +0700
+
+    feat: rebased
+
+1	0	src/main.rs
+";
+
+#[test]
+fn parse_with_author_date() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2022-12"))
+        .stdout(predicates::str::contains("Saturday"));
+}
+
+#[test]
+fn parse_with_commit_date() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt --date=commit
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--date=commit")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2023-01"))
+        .stdout(predicates::str::contains("2022-12").count(0))
+        .stdout(predicates::str::contains("Monday"));
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::path::Path;
+use std::process::Command;
+use tempfile::tempdir;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 1 Jun 2022 10:00:00 +0700
+
+    feat: first
+
+1	0	src/main.rs
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat: second
+
+2	0	src/lib.rs
+
+commit 920ef0b0ecb95ff653e9d6a4863b64662bb82834
+Author: Duyet Le <me@duyet.net>
+Date:   Thu, 1 Jun 2023 10:00:00 +0700
+
+    feat: third
+
+3	0	src/cli.rs
+";
+
+#[test]
+fn parse_with_since_until() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt --since 2023-01-01 --until 2023-03-01
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--since")
+        .arg("2023-01-01")
+        .arg("--until")
+        .arg("2023-03-01")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2023-02"))
+        .stdout(predicates::str::contains("2022-06").count(0))
+        .stdout(predicates::str::contains("2023-06").count(0));
+}
+
+#[test]
+fn parse_with_relative_since() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt --since 90.days.ago
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--since")
+        .arg("90.days.ago")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2022-06").count(0));
+}
+
+#[test]
+fn parse_with_invalid_since() {
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg("tests/")
+        .arg("--since")
+        .arg("next tuesday")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid date: next tuesday"));
+}
+
+/// Repository with one commit written in 2020, rebased in 2023
+fn rebased_repo(dir: &Path) {
+    let git = |args: &[&str]| {
+        common::git_with_env(
+            dir,
+            args,
+            &[
+                ("GIT_AUTHOR_DATE", "2020-06-01T10:00:00+07:00"),
+                ("GIT_COMMITTER_DATE", "2023-06-01T10:00:00+07:00"),
+            ],
+        )
+    };
+
+    git(&["init"]);
+    std::fs::write(dir.join("a.rs"), "fn main() {}\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-m", "feat: rebased"]);
+}
+
+#[test]
+fn since_until_on_the_date_field() {
+    let temp_dir = tempdir().unwrap();
+    rebased_repo(temp_dir.path());
+
+    // The author date is in the range, even if the commit date is not
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(temp_dir.path())
+        .args(["--since", "2020-01-01", "--until", "2020-12-31"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2020-06"));
+
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(temp_dir.path())
+        .args(["--since", "2023-01-01", "--date", "commit"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2023-06"));
+}
+
+#[test]
+fn since_pushed_down_to_git() {
+    let temp_dir = tempdir().unwrap();
+    rebased_repo(temp_dir.path());
+    let trace_dir = tempdir().unwrap();
+    let trace = trace_dir.path().join("trace.txt");
+
+    // With the author date, only --since is passed to git log
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(temp_dir.path())
+        .args(["--since", "2020-01-01", "--until", "2020-12-31"])
+        .env("GIT_TRACE", &trace)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2020-06"));
+
+    let trace = std::fs::read_to_string(&trace).unwrap();
+    let log = trace.lines().find(|l| l.contains(" log ")).unwrap();
+    assert!(log.contains("--since=2020-01-01"));
+    assert!(!log.contains("--until"));
+}
+
+#[test]
+fn empty_date_range() {
+    let temp_dir = tempdir().unwrap();
+    rebased_repo(temp_dir.path());
+
+    // No commit from git, empty tables instead of an error
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    let output = cmd
+        .arg(temp_dir.path())
+        .args(["--since", "2024-01-01", "--date", "commit"])
+        .args(["--output", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let rows = json["queries"]["commit_by_author"]["rows"]
+        .as_array()
+        .unwrap();
+    assert!(rows.is_empty());
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::git;
+use std::path::Path;
+use std::process::Command;
+use tempfile::tempdir;
+
+fn repo(root: &Path, name: &str, commits: usize) {
+    let dir = root.join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    git(&dir, &["init", "-b", "main"]);
+    for i in 0..commits {
+        std::fs::write(dir.join(format!("{}.rs", i)), "fn main() {}\n").unwrap();
+        git(&dir, &["add", "."]);
+        git(&dir, &["commit", "-m", &format!("commit {}", i)]);
+    }
+}
+
+#[test]
+fn commit_by_repo() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path();
+
+    repo(root, "org/api", 2);
+    repo(root, "org/team/web", 3);
+    repo(root, "archive/old", 1);
+
+    let json = common::json(root, &["--max-depth", "3", "--exclude", "archive"]);
+
+    let rows = json["queries"]["commit_by_repo"]["rows"]
+        .as_array()
+        .unwrap();
+    let commits = |repo: &str| {
+        rows.iter()
+            .find(|r| r["repo"] == repo)
+            .map(|r| r["commit"].clone())
+    };
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(commits("org/team/web"), Some(3.into()));
+    assert_eq!(commits("org/api"), Some(2.into()));
+    assert_eq!(commits("archive/old"), None);
+}
+
+#[test]
+fn no_repo_within_max_depth() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path();
+
+    repo(root, "org/api", 1);
+
+    // Only the direct children by default
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(root)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No .git found"));
+}
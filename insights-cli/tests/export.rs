@@ -0,0 +1,90 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+2	1	README.md
+";
+
+#[test]
+fn export_csv() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let export = temp_dir.path().join("commits.csv");
+
+    // $ insights numstat.txt --export commits.csv
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--export")
+        .arg(&export)
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(&export).unwrap();
+    let mut lines = content.lines();
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("commit,date,author_name,author_email,path,extension,added,deleted"));
+    assert_eq!(lines.count(), 2);
+    assert!(content.contains("src/main.rs"));
+}
+
+#[test]
+fn export_ndjson() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let export = temp_dir.path().join("commits.ndjson");
+
+    // $ insights numstat.txt --export commits.ndjson
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--export")
+        .arg(&export)
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(&export).unwrap();
+    assert_eq!(content.lines().count(), 2);
+    for line in content.lines() {
+        let row: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(row["author_name"], "Duyet Le");
+    }
+}
+
+#[test]
+fn export_parquet() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let export = temp_dir.path().join("commits.parquet");
+
+    // $ insights numstat.txt --export commits.parquet
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--export")
+        .arg(&export)
+        .assert()
+        .success();
+
+    // Parquet magic bytes
+    let content = std::fs::read(&export).unwrap();
+    assert!(content.starts_with(b"PAR1"));
+}
+
+#[test]
+fn export_unknown_format() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let export = temp_dir.path().join("commits.xlsx");
+
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--export")
+        .arg(&export)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Unsupported export format"));
+
+    assert!(!export.exists());
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Alice <alice@example.com>
+Date:   Sun, 5 Feb 2023 23:10:00 -0500
+
+    feat: second
+
+5	3	src/lib.rs
+";
+
+#[test]
+fn heatmap_in_commit_timezone() {
+    let json = common::json_of(NUMSTAT, &[]);
+
+    let rows = &json["queries"]["commit_heatmap"]["rows"];
+    assert_eq!(rows.as_array().unwrap().len(), 7);
+    assert_eq!(rows[0]["weekday"], "Mon");
+    assert_eq!(rows[1]["weekday"], "Tue");
+    assert_eq!(rows[1]["00"], 1);
+    assert_eq!(rows[6]["23"], 1);
+}
+
+#[test]
+fn heatmap_in_timezone() {
+    // $ insights numstat.txt --timezone UTC
+    let json = common::json_of(NUMSTAT, &["--timezone", "UTC"]);
+
+    let rows = &json["queries"]["commit_heatmap"]["rows"];
+    assert_eq!(rows[0]["17"], 1);
+    assert_eq!(rows[0]["04"], 1);
+    assert_eq!(rows[1]["00"], 0);
+
+    // $ insights numstat.txt --timezone +07:00
+    let json = common::json_of(NUMSTAT, &["--timezone", "+07:00"]);
+    assert_eq!(json["queries"]["commit_heatmap"]["rows"][1]["00"], 1);
+}
+
+#[test]
+fn heatmap_terminal_grid() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // Not a terminal, shaded without colors
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Commit by weekday and hour:"))
+        .stdout(predicates::str::contains("Tue ██"))
+        .stdout(predicates::str::contains("\x1b[").count(0));
+}
+
+#[test]
+fn heatmap_invalid_timezone() {
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg("tests/")
+        .args(["--timezone", "Asia/Saigon"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid timezone"));
+}
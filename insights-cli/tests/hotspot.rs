@@ -0,0 +1,58 @@
+mod common;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+10	0	src/parser/mod.rs
+1	0	README.md
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Alice <alice@example.com>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat: second
+
+5	3	src/parser/mod.rs
+1	1	src/main.rs
+";
+
+#[test]
+fn hotspot_files() {
+    let json = common::json_of(NUMSTAT, &[]);
+
+    let rows = &json["queries"]["hotspot_files"]["rows"];
+    assert_eq!(rows[0]["path"], "src/parser/mod.rs");
+    assert_eq!(rows[0]["commits"], 2);
+    assert_eq!(rows[0]["churn"], 18);
+    assert_eq!(rows[0]["authors"], 2);
+}
+
+#[test]
+fn hotspot_directories() {
+    let json = common::json_of(NUMSTAT, &[]);
+
+    let rows = &json["queries"]["hotspot_directories"]["rows"];
+    assert_eq!(rows[0]["directory"], "src");
+    assert_eq!(rows[0]["commits"], 2);
+    assert_eq!(rows[0]["churn"], 20);
+    assert_eq!(rows[0]["files"], 2);
+    assert_eq!(rows[1]["directory"], ".");
+}
+
+#[test]
+fn hotspot_directories_with_depth() {
+    // $ insights numstat.txt --depth 2
+    let json = common::json_of(NUMSTAT, &["--depth", "2"]);
+
+    let rows = json["queries"]["hotspot_directories"]["rows"]
+        .as_array()
+        .unwrap();
+    let dirs = rows
+        .iter()
+        .map(|r| r["directory"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(dirs, vec!["src/parser", "src", "."]);
+}
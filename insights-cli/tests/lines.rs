@@ -0,0 +1,66 @@
+mod common;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+10	2	src/main.rs
+3	0	README.md
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Alice <alice@example.com>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    refactor: remove dead code
+
+1	20	src/lib.rs
+
+commit 920ef0b0ecb95ff653e9d6a4863b64662bb82834
+Author: Alice <alice@example.com>
+Date:   Thu, 2 Feb 2023 10:00:00 +0700
+
+    chore: vendor dependencies
+
+6000	0	vendor/lib.js
+1	0	src/lib.rs
+";
+
+fn find<'a>(rows: &'a serde_json::Value, key: &str, value: &str) -> &'a serde_json::Value {
+    rows.as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r[key] == value)
+        .unwrap()
+}
+
+#[test]
+fn lines_by_author_month_language() {
+    let json = common::json_of(NUMSTAT, &[]);
+
+    let rows = &json["queries"]["lines_by_author"]["rows"];
+    let alice = find(rows, "author_name", "Alice");
+    assert_eq!(alice["added"], 6002);
+    assert_eq!(alice["deleted"], 20);
+    assert_eq!(alice["net"], 5982);
+
+    let rows = &json["queries"]["lines_by_month"]["rows"];
+    assert_eq!(find(rows, "year_month", "2023-01")["net"], 11);
+
+    let rows = &json["queries"]["lines_by_language"]["rows"];
+    assert_eq!(find(rows, "language", "rs")["net"], -10);
+}
+
+#[test]
+fn lines_without_outlier_commits() {
+    // $ insights numstat.txt --max-commit-lines 1000
+    let json = common::json_of(NUMSTAT, &["--max-commit-lines", "1000"]);
+
+    let rows = &json["queries"]["lines_by_author"]["rows"];
+    let alice = find(rows, "author_name", "Alice");
+    assert_eq!(alice["added"], 1);
+    assert_eq!(alice["net"], -19);
+
+    assert_eq!(json["queries"]["summary"]["rows"][0]["commit_count"], 2);
+}
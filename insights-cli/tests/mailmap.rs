@@ -0,0 +1,70 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::git;
+use std::process::Command;
+use tempfile::tempdir;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: duyet <lvduit08@gmail.com>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+";
+
+#[test]
+fn parse_with_mailmap_file() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    let mailmap = temp_dir.path().join(".mailmap");
+    std::fs::write(&mailmap, "Duyet Le <me@duyet.net> <lvduit08@gmail.com>\n").unwrap();
+
+    // $ insights numstat.txt --mailmap .mailmap
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--mailmap")
+        .arg(&mailmap)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Duyet Le"))
+        .stdout(predicates::str::contains("duyet").count(0));
+}
+
+#[test]
+fn parse_git_folder_with_mailmap() {
+    let temp_dir = tempdir().unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    git(temp_dir_path, &["init"]);
+    std::fs::write(temp_dir_path.join("main.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(
+        temp_dir_path.join(".mailmap"),
+        "Duyet Le <me@duyet.net> <lvduit08@gmail.com>\n",
+    )
+    .unwrap();
+    git(temp_dir_path, &["add", "."]);
+
+    // Committed with the old identity, mapped by the .mailmap
+    git(
+        temp_dir_path,
+        &[
+            "-c",
+            "user.name=duyet",
+            "-c",
+            "user.email=lvduit08@gmail.com",
+            "commit",
+            "-m",
+            "feat: init",
+        ],
+    );
+
+    // $ insights <git dir>
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(temp_dir_path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Duyet Le"))
+        .stdout(predicates::str::contains("duyet").count(0));
+}
@@ -0,0 +1,90 @@
+mod common;
+
+const NUMSTAT: &str = "commit 920ef0b0ecb95ff653e9d6a4863b64662bb82834
+Merge: 5980700 1d41716
+Author: Duyet Le <me@duyet.net>
+Date:   Fri, 3 Mar 2023 10:00:00 +0700
+
+    Merge pull request #42 from duyet/renovate/all-minor-patch
+
+    chore(deps): update all non-major dependencies
+
+commit 4d07e012d8a31d7a19f4c3461d11e0ad83868d6d
+Merge: 5980700 1d41716
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 1 Mar 2023 10:00:00 +0700
+
+    Merge pull request #41 from alice/feat/parser
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat: parse tags
+
+5	3	src/lib.rs
+
+commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Merge: 5980700 1d41716
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    Merge pull request #40 from alice/docs
+
+1	0	README.md
+";
+
+#[test]
+fn pull_requests_by_month() {
+    let json = common::json_of(NUMSTAT, &[]);
+
+    let rows = &json["queries"]["pull_requests_by_month"]["rows"];
+    assert_eq!(rows[0]["year_month"], "2023-01");
+    assert_eq!(rows[0]["pull_requests"], 1);
+    assert_eq!(rows[1]["year_month"], "2023-03");
+    assert_eq!(rows[1]["pull_requests"], 2);
+    assert_eq!(rows[1]["owners"], 2);
+
+    // The latest first
+    let rows = &json["queries"]["pull_requests"]["rows"];
+    assert_eq!(rows[0]["number"], 42);
+    assert_eq!(rows[0]["owner"], "duyet");
+    assert_eq!(rows[0]["branch"], "renovate/all-minor-patch");
+    assert_eq!(rows[0]["merged_by"], "Duyet Le");
+}
+
+#[test]
+fn merges_exclude() {
+    // $ insights numstat.txt --merges exclude
+    let json = common::json_of(NUMSTAT, &["--merges", "exclude"]);
+
+    assert_eq!(json["queries"]["summary"]["rows"][0]["commit_count"], 1);
+    assert!(json["queries"].get("pull_requests").is_none());
+}
+
+#[test]
+fn merges_only() {
+    // $ insights numstat.txt --merges only
+    let json = common::json_of(NUMSTAT, &["--merges", "only"]);
+
+    assert_eq!(json["queries"]["summary"]["rows"][0]["commit_count"], 1);
+    assert_eq!(json["queries"]["summary"]["rows"][0]["added"], 1);
+}
+
+#[test]
+fn pull_requests_in_date_range() {
+    // $ insights numstat.txt --since 2023-03-01
+    let json = common::json_of(NUMSTAT, &["--since", "2023-03-01"]);
+
+    let rows = &json["queries"]["pull_requests_by_month"]["rows"];
+    assert_eq!(rows.as_array().unwrap().len(), 1);
+    assert_eq!(rows[0]["year_month"], "2023-03");
+    assert_eq!(rows[0]["pull_requests"], 2);
+
+    let rows = json["queries"]["pull_requests"]["rows"].as_array().unwrap();
+    assert!(rows.iter().all(|r| r["number"] != 40));
+
+    // $ insights numstat.txt --ignore-author "Duyet Le"
+    let json = common::json_of(NUMSTAT, &["--ignore-author", "Duyet Le"]);
+    assert!(json["queries"].get("pull_requests").is_none());
+}
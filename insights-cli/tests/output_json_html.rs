@@ -18,22 +18,33 @@ fn output_json() {
     println!("{:#}", json);
 
     // Check json
-    assert!(!json["commit_by_author"].as_object().unwrap().is_empty());
-    assert!(!json["commit_by_author"]["author_name"]
-        .as_array()
-        .unwrap()
-        .is_empty());
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["layout"], "rows");
+    let query = &json["queries"]["commit_by_author"];
+    assert_eq!(query["title"], "Commit by author");
+    assert_eq!(query["fields"][0]["name"], "author_name");
+    assert_eq!(query["fields"][0]["type"], "string");
+    assert_eq!(query["fields"][1]["type"], "integer");
+    assert!(!query["rows"].as_array().unwrap().is_empty());
+    assert!(query["rows"][0]["commit"].is_u64());
 }
 
 #[test]
 fn output_html() {
     let github_url = "https://github.com/duyet/git-insights-rs.git";
 
-    // $ insights https://github.com/duyet/git-insights-rs.git https://github.com/duyet/git-insights-rs.git
+    // $ insights https://github.com/duyet/git-insights-rs.git --output=html
     let mut cmd = Command::cargo_bin("girs").unwrap();
     cmd.arg(github_url)
         .arg("--output=html")
         .assert()
-        .failure()
-        .stderr(predicates::str::contains("Not implemented yet"));
+        .success()
+        .stdout(predicates::str::starts_with("<!DOCTYPE html>"))
+        .stdout(predicates::str::contains("<h2>Commit by author</h2>"))
+        .stdout(predicates::str::contains("<h2>Top languages</h2>"))
+        .stdout(predicates::str::contains("<svg"))
+        .stdout(predicates::str::contains("Duyet Le"))
+        // Self-contained, no external assets
+        .stdout(predicates::str::contains("<script src").count(0))
+        .stdout(predicates::str::contains("<link").count(0));
 }
@@ -0,0 +1,52 @@
+mod common;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+2	1	README.md
+";
+
+#[test]
+fn output_json_rows() {
+    // $ insights numstat.txt --output json
+    let json = common::json_of(NUMSTAT, &[]);
+
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["layout"], "rows");
+
+    let summary = &json["queries"]["summary"];
+    assert_eq!(summary["title"], "Summary");
+    assert_eq!(summary["rows"][0]["commit_count"], 1);
+    assert_eq!(summary["rows"][0]["added"], 3);
+    // Dates are ISO-8601 in UTC
+    assert_eq!(summary["rows"][0]["last commit"], "2023-01-09T17:35:39Z");
+
+    let fields = summary["fields"].as_array().unwrap();
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|f| f["name"] == name)
+            .map(|f| f["type"].clone())
+            .unwrap()
+    };
+    assert_eq!(field("commit_count"), "integer");
+    assert_eq!(field("authors"), "array");
+    assert_eq!(field("last commit"), "datetime");
+}
+
+#[test]
+fn output_json_columns() {
+    // $ insights numstat.txt --output json --json-layout columns
+    let json = common::json_of(NUMSTAT, &["--json-layout", "columns"]);
+
+    assert_eq!(json["layout"], "columns");
+
+    let query = &json["queries"]["commit_by_author"];
+    assert!(query.get("rows").is_none());
+    assert_eq!(query["columns"]["author_name"][0], "Duyet Le");
+    assert_eq!(query["columns"]["commit"][0], 1);
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet | Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+2	1	README.md
+";
+
+#[test]
+fn output_markdown() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt --output=markdown
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--output=markdown")
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("# Git Insights"))
+        .stdout(predicates::str::contains("## Commit by author\n"))
+        .stdout(predicates::str::contains("## Top languages\n"))
+        .stdout(predicates::str::contains("| author_name | commit |"))
+        .stdout(predicates::str::contains("| --- | ---: |"))
+        // Pipes are escaped in the cells
+        .stdout(predicates::str::contains("| Duyet \\| Le | 1 |"));
+}
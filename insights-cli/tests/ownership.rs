@@ -0,0 +1,72 @@
+mod common;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+90	0	src/main.rs
+50	0	docs/index.md
+1	0	README.md
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Alice <alice@example.com>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat: second
+
+10	0	src/lib.rs
+50	0	docs/guide.md
+";
+
+#[test]
+fn ownership_by_directory() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let codeowners = temp_dir.path().join("CODEOWNERS");
+
+    // $ insights numstat.txt --output json --codeowners CODEOWNERS
+    let json = common::json(&path, &["--codeowners", codeowners.to_str().unwrap()]);
+
+    let rows = json["queries"]["ownership"]["rows"].as_array().unwrap();
+    let row = |dir: &str| rows.iter().find(|r| r["directory"] == dir).unwrap();
+
+    // 90% by one author
+    assert_eq!(row("src")["owner"], "Duyet Le");
+    assert_eq!(row("src")["owner_share"], 90.0);
+    assert_eq!(row("src")["bus_factor"], 1);
+    assert_eq!(row("src")["at_risk"], true);
+
+    // Shared equally
+    assert_eq!(row("docs")["authors"], 2);
+    assert_eq!(row("docs")["bus_factor"], 2);
+    assert_eq!(row("docs")["at_risk"], false);
+
+    // Bus factor 1 first
+    assert_eq!(rows.last().unwrap()["directory"], "docs");
+
+    let codeowners = std::fs::read_to_string(&codeowners).unwrap();
+    assert!(codeowners.starts_with("# DRAFT"));
+    let lines = codeowners
+        .lines()
+        .filter(|l| !l.starts_with('#'))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        vec![
+            "* me@duyet.net",
+            "/docs/ alice@example.com me@duyet.net",
+            "/src/ me@duyet.net",
+        ]
+    );
+}
+
+#[test]
+fn ownership_with_threshold() {
+    // $ insights numstat.txt --ownership-threshold 95
+    let json = common::json_of(NUMSTAT, &["--ownership-threshold", "95"]);
+
+    let rows = json["queries"]["ownership"]["rows"].as_array().unwrap();
+    let src = rows.iter().find(|r| r["directory"] == "src").unwrap();
+    assert_eq!(src["bus_factor"], 2);
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Duyet Le <me@duyet.net>
+Date:   yesterday
+
+    feat: second
+
+2	0	src/lib.rs
+";
+
+#[test]
+fn parse_invalid_commit_lenient() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Commit by author"))
+        .stderr(predicates::str::contains(
+            "warning: line 11: invalid date `yesterday`",
+        ));
+}
+
+#[test]
+fn parse_invalid_commit_strict() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt --strict
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("--strict")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "line 11: invalid date `yesterday`",
+        ));
+}
+
+#[test]
+fn parse_multiple_with_invalid_path() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights numstat.txt not_found.txt
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .arg("not_found.txt")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "warning: not_found.txt: Invalid path: not_found.txt",
+        ));
+}
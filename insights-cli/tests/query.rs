@@ -0,0 +1,74 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+2	1	README.md
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Duet <duet@duyet.net>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat: second
+
+5	3	src/lib.rs
+";
+
+#[test]
+fn query_commits_table() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights query "SELECT ..." numstat.txt
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg("query")
+        .arg("SELECT author_name, SUM(added) AS total_added FROM commits GROUP BY author_name")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("total_added"))
+        .stdout(predicates::str::contains("Duyet Le"))
+        .stdout(predicates::str::contains("Duet"))
+        // Only the query result, not the default queries
+        .stdout(predicates::str::contains("Top languages").count(0));
+}
+
+#[test]
+fn query_commits_summary_json() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    // $ insights query "SELECT ..." numstat.txt --output json
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    let output = cmd
+        .arg("query")
+        .arg("SELECT commit, files FROM commits_summary ORDER BY files DESC")
+        .arg(&path)
+        .arg("--output")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["queries"]["query"]["rows"][0]["files"], 2);
+    assert_eq!(json["queries"]["query"]["rows"][1]["files"], 1);
+}
+
+#[test]
+fn query_invalid_sql() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg("query")
+        .arg("SELECT * FROM not_a_table")
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Running query"));
+}
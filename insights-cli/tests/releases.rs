@@ -0,0 +1,111 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::git;
+use std::path::Path;
+use std::process::Command;
+use tempfile::tempdir;
+
+const NUMSTAT: &str = "commit 920ef0b0ecb95ff653e9d6a4863b64662bb82834 (tag: v1.1.0)
+Author: Alice <alice@example.com>
+Date:   Fri, 3 Mar 2023 10:00:00 +0700
+
+    fix: typo
+
+2	2	src/cli.rs
+1	0	docs/index.md
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat: parse tags
+
+5	3	src/lib.rs
+
+commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1 (tag: v1.0.0)
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+10	0	src/main.rs
+3	0	README.md
+";
+
+#[test]
+fn release_report() {
+    let json = common::json_of(NUMSTAT, &[]);
+    let rows = &json["queries"]["releases"]["rows"];
+    assert_eq!(rows.as_array().unwrap().len(), 2);
+
+    // The latest first
+    assert_eq!(rows[0]["release"], "v1.1.0");
+    assert_eq!(rows[0]["date"], "2023-03-03");
+    assert_eq!(rows[0]["commits"], 2);
+    assert_eq!(rows[0]["contributors"], 2);
+    assert_eq!(rows[0]["added"], 8);
+    assert_eq!(rows[0]["deleted"], 5);
+    assert_eq!(rows[0]["top_languages"], "rs, md");
+    assert_eq!(rows[0]["new_contributors"], "Alice");
+
+    assert_eq!(rows[1]["release"], "v1.0.0");
+    assert_eq!(rows[1]["commits"], 1);
+    assert_eq!(rows[1]["new_contributors"], "Duyet Le");
+}
+
+#[test]
+fn no_release_report_without_tags() {
+    let numstat = NUMSTAT
+        .replace("(tag: v1.1.0)", "")
+        .replace("(tag: v1.0.0)", "");
+    let (_temp_dir, path) = common::numstat_file(&numstat);
+
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Releases:").count(0));
+}
+
+/// Commit `file` on the current branch, with the author and commit dates
+fn commit(dir: &Path, file: &str, date: &str) {
+    std::fs::write(dir.join(file), file).unwrap();
+    git(dir, &["add", "."]);
+    common::git_with_env(
+        dir,
+        &["commit", "-m", file],
+        &[("GIT_AUTHOR_DATE", date), ("GIT_COMMITTER_DATE", date)],
+    );
+}
+
+#[test]
+fn release_report_of_branched_history() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+
+    // v1.0.1 is a backport on a maintenance branch, tagged before v1.1.0
+    git(dir, &["init", "-b", "main"]);
+    commit(dir, "a.rs", "2023-01-01T00:00:00+00:00");
+    git(dir, &["tag", "v1.0.0"]);
+    commit(dir, "b.rs", "2023-02-01T00:00:00+00:00");
+    git(dir, &["checkout", "-b", "maint", "v1.0.0"]);
+    commit(dir, "fix.rs", "2023-03-01T00:00:00+00:00");
+    git(dir, &["tag", "v1.0.1"]);
+    git(dir, &["checkout", "main"]);
+    commit(dir, "c.rs", "2023-04-01T00:00:00+00:00");
+    git(dir, &["tag", "v1.1.0"]);
+
+    let json = common::json(dir, &[]);
+    let rows = json["queries"]["releases"]["rows"].as_array().unwrap();
+    let commits = |release: &str| {
+        rows.iter()
+            .find(|r| r["release"] == release)
+            .map(|r| r["commits"].clone())
+    };
+
+    // b.rs is only in v1.1.0, even if v1.0.1 is the first tag after it
+    assert_eq!(commits("v1.1.0"), Some(2.into()));
+    assert_eq!(commits("v1.0.1"), Some(1.into()));
+    assert_eq!(commits("v1.0.0"), Some(1.into()));
+}
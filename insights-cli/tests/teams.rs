@@ -0,0 +1,97 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+const NUMSTAT: &str = "commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+Author: Duyet Le <me@duyet.net>
+Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+    feat: first
+
+1	0	src/main.rs
+
+commit 598070021341c247fa041baac291fa1bfa0133b4
+Author: Alice <alice@infra.example.com>
+Date:   Wed, 1 Feb 2023 10:00:00 +0700
+
+    feat: second
+
+5	3	src/lib.rs
+
+commit 920ef0b0ecb95ff653e9d6a4863b64662bb82834
+Author: Bob <bob@example.com>
+Date:   Thu, 2 Feb 2023 10:00:00 +0700
+
+    feat: third
+
+2	2	src/cli.rs
+";
+
+const TEAMS: &str = r#"
+Platform = ["me@duyet.net", "*@INFRA.example.com"]
+"#;
+
+#[test]
+fn commit_by_team() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let teams = temp_dir.path().join("teams.toml");
+    std::fs::write(&teams, TEAMS).unwrap();
+
+    // $ insights numstat.txt --teams-file teams.toml --output json
+    let json = common::json(&path, &["--teams-file", teams.to_str().unwrap()]);
+
+    let rows = &json["queries"]["commit_by_team"]["rows"];
+    assert_eq!(rows[0]["team"], "Platform");
+    assert_eq!(rows[0]["commit"], 2);
+    assert_eq!(rows[0]["authors"], 2);
+    assert_eq!(rows[0]["added"], 6);
+    assert_eq!(rows[1]["team"], "(no team)");
+    assert_eq!(rows[1]["commit"], 1);
+
+    let rows = json["queries"]["commit_by_team_by_month"]["rows"]
+        .as_array()
+        .unwrap();
+    assert!(rows
+        .iter()
+        .any(|r| r["team"] == "Platform" && r["year_month"] == "2023-02" && r["commit"] == 1));
+}
+
+#[test]
+fn first_team_in_file_order() {
+    let (temp_dir, path) = common::numstat_file(NUMSTAT);
+    let teams = temp_dir.path().join("teams.toml");
+    std::fs::write(
+        &teams,
+        r#"
+Zeta = ["*@infra.example.com"]
+Alpha = ["Alice", "Bob"]
+"#,
+    )
+    .unwrap();
+
+    // Alice is in both teams, the first one in the file wins
+    let json = common::json(&path, &["--teams-file", teams.to_str().unwrap()]);
+
+    let rows = json["queries"]["commit_by_team"]["rows"]
+        .as_array()
+        .unwrap();
+    let commits = |team: &str| {
+        rows.iter()
+            .find(|r| r["team"] == team)
+            .map(|r| r["commit"].clone())
+    };
+    assert_eq!(commits("Zeta"), Some(1.into()));
+    assert_eq!(commits("Alpha"), Some(1.into()));
+}
+
+#[test]
+fn no_team_queries_without_teams() {
+    let (_temp_dir, path) = common::numstat_file(NUMSTAT);
+
+    let mut cmd = Command::cargo_bin("girs").unwrap();
+    cmd.arg(&path)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Commit by team").count(0));
+}
@@ -0,0 +1,77 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref HEADER_RE: Regex = Regex::new(
+        r"^(?P<type>[A-Za-z]+)(\((?P<scope>[^()]*)\))?(?P<breaking>!)?:\s+(?P<subject>.+)$"
+    )
+    .unwrap();
+    static ref BREAKING_RE: Regex = Regex::new(r"(?m)^BREAKING[ -]CHANGE:").unwrap();
+}
+
+/// Header of a commit message following https://www.conventionalcommits.org
+///
+/// ```txt
+/// feat(parser)!: read the history with libgit2
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalCommit {
+    /// Type of the change, lowercased, e.g. `feat`, `fix` or `chore`
+    pub kind: String,
+    pub scope: Option<String>,
+    /// `!` before the colon or a `BREAKING CHANGE:` footer
+    pub breaking: bool,
+    pub subject: String,
+}
+
+impl ConventionalCommit {
+    /// None if the first line of the message is not a conventional commit header
+    pub fn parse(message: &str) -> Option<Self> {
+        let header = message.lines().map(str::trim).find(|l| !l.is_empty())?;
+        let captures = HEADER_RE.captures(header)?;
+
+        Some(Self {
+            kind: captures["type"].to_lowercase(),
+            scope: captures
+                .name("scope")
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|s| !s.is_empty()),
+            breaking: captures.name("breaking").is_some() || BREAKING_RE.is_match(message),
+            subject: captures["subject"].trim().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conventional_commit() {
+        let c = ConventionalCommit::parse("fix: timestamp is invalid\n").unwrap();
+        assert_eq!(c.kind, "fix");
+        assert_eq!(c.scope, None);
+        assert!(!c.breaking);
+        assert_eq!(c.subject, "timestamp is invalid");
+
+        let c = ConventionalCommit::parse("Feat(parser)!: use libgit2\n").unwrap();
+        assert_eq!(c.kind, "feat");
+        assert_eq!(c.scope.as_deref(), Some("parser"));
+        assert!(c.breaking);
+
+        let c = ConventionalCommit::parse(
+            "refactor(cli): rename flags\n\nBREAKING CHANGE: --out is now --output\n",
+        )
+        .unwrap();
+        assert!(c.breaking);
+
+        assert_eq!(ConventionalCommit::parse("Update README.md\n"), None);
+        assert_eq!(
+            ConventionalCommit::parse(
+                "Merge pull request #42 from duyet/renovate\nchore(deps): update\n"
+            ),
+            None
+        );
+        assert_eq!(ConventionalCommit::parse(""), None);
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Months, NaiveDate, NaiveDateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref RELATIVE_RE: Regex = Regex::new(
+        r"^(?P<n>\d+)[.\s]+(?P<unit>second|minute|hour|day|week|month|year)s?[.\s]+ago$"
+    )
+    .unwrap();
+}
+
+/// Parse a date the way `git log --since` does for the common forms:
+/// `2023-01-31`, `2023-01-31 10:00:00`, RFC 3339, RFC 2822,
+/// `now`, `today`, `yesterday` and relative dates like `90.days.ago` or `2 weeks ago`.
+pub fn parse_date(s: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+    let lower = s.to_lowercase();
+    let now = Utc::now();
+    let midnight = |d: NaiveDate| d.and_hms_opt(0, 0, 0).map(|d| d.and_utc());
+
+    let date = match lower.as_str() {
+        "now" => Some(now),
+        "today" => midnight(now.date_naive()),
+        "yesterday" => midnight(now.date_naive() - Duration::days(1)),
+        _ => None,
+    };
+
+    if let Some(date) = date {
+        return Ok(date);
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(s) {
+        return Ok(date.with_timezone(&Utc));
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc2822(s) {
+        return Ok(date.with_timezone(&Utc));
+    }
+
+    if let Ok(date) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return Ok(date.and_utc());
+    }
+
+    if let Some(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(midnight)
+    {
+        return Ok(date);
+    }
+
+    let captures = RELATIVE_RE
+        .captures(&lower)
+        .ok_or_else(|| anyhow!("Invalid date: {}", s))?;
+    let n: u32 = captures["n"].parse()?;
+
+    let date = match &captures["unit"] {
+        "second" => now.checked_sub_signed(Duration::seconds(n.into())),
+        "minute" => now.checked_sub_signed(Duration::minutes(n.into())),
+        "hour" => now.checked_sub_signed(Duration::hours(n.into())),
+        "day" => now.checked_sub_signed(Duration::days(n.into())),
+        "week" => now.checked_sub_signed(Duration::weeks(n.into())),
+        "month" => now.checked_sub_months(Months::new(n)),
+        "year" => now.checked_sub_months(Months::new(n * 12)),
+        _ => None,
+    };
+
+    date.ok_or_else(|| anyhow!("Invalid date: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("2023-01-31").unwrap().to_rfc3339(),
+            "2023-01-31T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_date("2023-01-31 10:20:30").unwrap().to_rfc3339(),
+            "2023-01-31T10:20:30+00:00"
+        );
+        assert_eq!(
+            parse_date("2023-01-31T10:20:30+07:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2023-01-31T03:20:30+00:00"
+        );
+        assert_eq!(
+            parse_date("Tue, 10 Jan 2023 00:35:39 +0700")
+                .unwrap()
+                .to_rfc3339(),
+            "2023-01-09T17:35:39+00:00"
+        );
+
+        let date = parse_date("90.days.ago").unwrap();
+        assert_eq!((Utc::now() - date).num_days(), 90);
+
+        let date = parse_date("2 weeks ago").unwrap();
+        assert_eq!((Utc::now() - date).num_days(), 14);
+
+        assert!(parse_date("1.year.ago").unwrap() < parse_date("11.months.ago").unwrap());
+        assert!(parse_date("yesterday").unwrap() < parse_date("today").unwrap());
+
+        assert_eq!(
+            parse_date("next tuesday").unwrap_err().to_string(),
+            "Invalid date: next tuesday"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use log::debug;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Working directory with a `.git` folder, or a `.git` file for the worktrees
+/// and submodules, or a bare repository
+pub fn is_repo(path: &Path) -> bool {
+    path.join(".git").exists()
+        || (path.join("HEAD").is_file()
+            && path.join("objects").is_dir()
+            && path.join("refs").is_dir())
+}
+
+/// Find the git repositories in `root`, up to `max_depth` levels of folders,
+/// skipping the folders matching the `exclude` globs. A repository is not searched for nested ones.
+pub fn discover(root: &Path, max_depth: usize, exclude: &[String]) -> Result<Vec<PathBuf>> {
+    let exclude = exclude
+        .iter()
+        .map(|glob| glob_to_regex(glob))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut repos = vec![];
+    walk(root, root, 1, max_depth, &exclude, &mut repos);
+    repos.sort();
+
+    debug!(
+        "Scanning `{}`, found {} git dir(s)",
+        root.display(),
+        repos.len()
+    );
+
+    Ok(repos)
+}
+
+fn walk(
+    root: &Path,
+    dir: &Path,
+    depth: usize,
+    max_depth: usize,
+    exclude: &[Regex],
+    repos: &mut Vec<PathBuf>,
+) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!("Skipping {}: {}", dir.display(), e);
+            return;
+        }
+    };
+
+    for path in entries.flatten().map(|e| e.path()) {
+        // Not following the symlinks, they might loop
+        if !path.is_dir() || path.is_symlink() || path.ends_with(".git") {
+            continue;
+        }
+
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy();
+        if exclude.iter().any(|re| re.is_match(&relative)) {
+            debug!("Excluding {}", path.display());
+            continue;
+        }
+
+        if is_repo(&path) {
+            repos.push(path);
+        } else if depth < max_depth {
+            walk(root, &path, depth + 1, max_depth, exclude, repos);
+        }
+    }
+}
+
+/// `*` matches within a folder name, `**` across folders and `?` a single character.
+/// A glob without `/` matches the folder name at any level, e.g. `node_modules`
+fn glob_to_regex(glob: &str) -> Result<Regex> {
+    let mut re = String::new();
+    let mut chars = glob.trim_end_matches('/').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    let re = if glob.contains('/') {
+        format!("^{}$", re)
+    } else {
+        format!("(^|/){}$", re)
+    };
+
+    Ok(Regex::new(&re)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_glob_to_regex() {
+        let re = glob_to_regex("node_modules").unwrap();
+        assert!(re.is_match("node_modules"));
+        assert!(re.is_match("org/node_modules"));
+        assert!(!re.is_match("org/node_modules2"));
+
+        let re = glob_to_regex("org/*-archive").unwrap();
+        assert!(re.is_match("org/repo-archive"));
+        assert!(!re.is_match("org/team/repo-archive"));
+
+        let re = glob_to_regex("**/vendor").unwrap();
+        assert!(re.is_match("org/team/vendor"));
+    }
+
+    #[test]
+    fn test_discover() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let git = |args: &[&str]| crate::test_utils::git(root, args);
+
+        std::fs::create_dir_all(root.join("org/team")).unwrap();
+        git(&["init", "org/team/repo"]);
+        git(&["init", "org/team/repo/nested"]);
+        git(&["init", "--bare", "org/bare.git"]);
+        git(&["init", "archive/old"]);
+
+        // .git file of a worktree or a submodule
+        std::fs::create_dir_all(root.join("org/worktree")).unwrap();
+        std::fs::write(root.join("org/worktree/.git"), "gitdir: ../bare.git\n").unwrap();
+
+        let relative = |repos: Vec<PathBuf>| {
+            repos
+                .iter()
+                .map(|p| p.strip_prefix(root).unwrap().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            relative(discover(root, 3, &[]).unwrap()),
+            vec![
+                "archive/old",
+                "org/bare.git",
+                "org/team/repo",
+                "org/worktree"
+            ]
+        );
+        assert_eq!(
+            relative(discover(root, 2, &["archive".to_string()]).unwrap()),
+            vec!["org/bare.git", "org/worktree"]
+        );
+        assert!(discover(root, 1, &[]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Errors found while parsing the git log
+#[derive(Debug)]
+pub enum Error {
+    /// The `commit <hash>` line is missing or the hash is invalid
+    InvalidCommit { line: usize, content: String },
+    /// The `Date:` line is not a RFC 2822 date
+    InvalidDate { line: usize, date: String },
+    /// The commit cannot be read from the repository
+    Commit { commit: String, message: String },
+    /// Failed to read the input
+    Io(std::io::Error),
+    /// Failed to read the history of a path or a remote repository
+    Path { path: PathBuf, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidCommit { line, content } => {
+                write!(f, "line {}: invalid commit `{}`", line, content)
+            }
+            Error::InvalidDate { line, date } => {
+                write!(f, "line {}: invalid date `{}`", line, date)
+            }
+            Error::Commit { commit, message } => write!(f, "commit {}: {}", commit, message),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Path { path, message } => write!(f, "{}: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// How to handle the commits that cannot be parsed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail on the first error
+    Strict,
+    /// Skip the invalid commits and paths, report them as diagnostics
+    #[default]
+    Lenient,
+}
+
+/// Parsed commits and the errors skipped in [`Mode::Lenient`]
+#[derive(Debug, Default)]
+pub struct Parsed {
+    pub numstats: Vec<crate::Numstat>,
+    pub diagnostics: Vec<Error>,
+}
+
+impl Parsed {
+    /// Collect the results, stop on the first error in [`Mode::Strict`]
+    /// or on the first I/O error in any mode
+    pub fn collect<I>(results: I, mode: Mode) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = Result<crate::Numstat, Error>>,
+    {
+        let mut parsed = Parsed::default();
+
+        for result in results {
+            match result {
+                Ok(numstat) => parsed.numstats.push(numstat),
+                // Cannot continue reading after an I/O error
+                Err(e @ Error::Io(_)) => return Err(e),
+                Err(e) if mode == Mode::Strict => return Err(e),
+                Err(e) => parsed.diagnostics.push(e),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn extend(&mut self, other: Parsed) {
+        self.numstats.extend(other.numstats);
+        self.diagnostics.extend(other.diagnostics);
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
 use log::debug;
+use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::thread::JoinHandle;
 
 pub fn clone(url: &str, path: &PathBuf) -> Result<()> {
     debug!("Cloning {} to {}", url, path.display());
@@ -24,14 +27,17 @@ pub fn clone(url: &str, path: &PathBuf) -> Result<()> {
 }
 
 pub fn get_log(path: &PathBuf) -> Result<String> {
-    let cmd = "git log --all --numstat --date=rfc";
+    let cmd = "git log --all --parents --numstat --date=rfc --format=fuller --decorate=short";
 
     debug!("Running {}", cmd);
     let output = Command::new("git")
         .arg("log")
         .arg("--all")
+        .arg("--parents")
         .arg("--numstat")
         .arg("--date=rfc")
+        .arg("--format=fuller")
+        .arg("--decorate=short")
         .current_dir(path)
         .output()
         .with_context(|| format!("Running command: `{}`", cmd))?;
@@ -54,6 +60,213 @@ pub fn get_log(path: &PathBuf) -> Result<String> {
     }
 }
 
+/// Extra arguments of `git log` to limit the history,
+/// and how to find the repositories in a folder
+#[derive(Debug, Clone)]
+pub struct LogOptions {
+    /// `--since`
+    pub since: Option<DateTime<Utc>>,
+    /// `--until`
+    pub until: Option<DateTime<Utc>>,
+    /// Branches or revision ranges to read instead of `--all`, e.g. `main` or `v1.0..v2.0`
+    pub revisions: Vec<String>,
+    pub merges: Merges,
+    /// Levels of folders to search for repositories, 1 for the direct children only
+    pub max_depth: usize,
+    /// Globs of the folders to skip while searching, e.g. `node_modules` or `org/*-archive`
+    pub exclude: Vec<String>,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self {
+            since: None,
+            until: None,
+            revisions: vec![],
+            merges: Merges::default(),
+            max_depth: 1,
+            exclude: vec![],
+        }
+    }
+}
+
+/// Which commits to read depending on their number of parents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Merges {
+    /// All the commits, the merge commits have no stats like `git log --numstat`
+    #[default]
+    Include,
+    /// `--no-merges`
+    Exclude,
+    /// `--merges`, with the stats of the merge against its first parent
+    Only,
+}
+
+impl Merges {
+    /// Whether a commit with these parents is read
+    pub fn matches(&self, is_merge: bool) -> bool {
+        match self {
+            Merges::Include => true,
+            Merges::Exclude => !is_merge,
+            Merges::Only => is_merge,
+        }
+    }
+}
+
+impl LogOptions {
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.revisions.is_empty() {
+            args.push("--all".to_string());
+        }
+
+        if let Some(since) = self.since {
+            args.push(format!("--since={}", since.to_rfc3339()));
+        }
+        if let Some(until) = self.until {
+            args.push(format!("--until={}", until.to_rfc3339()));
+        }
+
+        match self.merges {
+            Merges::Include => {}
+            Merges::Exclude => args.push("--no-merges".to_string()),
+            Merges::Only => {
+                args.push("--merges".to_string());
+                args.push("--diff-merges=first-parent".to_string());
+            }
+        }
+
+        // Revisions last, `--` so they are never taken as paths
+        if !self.revisions.is_empty() {
+            args.extend(self.revisions.iter().cloned());
+            args.push("--".to_string());
+        }
+
+        args
+    }
+}
+
+/// Output of `git log --parents --numstat --date=rfc --format=fuller --decorate=short --source`,
+/// read while git is running
+pub struct LogStream {
+    child: Child,
+    stdout: BufReader<ChildStdout>,
+    /// Read on its own thread, git would block on a full stderr pipe otherwise
+    stderr: Option<JoinHandle<String>>,
+    path: PathBuf,
+    finished: bool,
+}
+
+pub fn stream_log(path: &PathBuf, options: &LogOptions) -> Result<LogStream> {
+    let args = options.args();
+    let cmd = format!(
+        "git log --parents --numstat --date=rfc --format=fuller --decorate=short --source {}",
+        args.join(" ")
+    );
+
+    debug!("Running {}", cmd);
+    let mut child = Command::new("git")
+        .arg("log")
+        .arg("--parents")
+        .arg("--numstat")
+        .arg("--date=rfc")
+        .arg("--format=fuller")
+        .arg("--decorate=short")
+        .arg("--source")
+        .args(&args)
+        .current_dir(path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Running command: `{}`", cmd))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("Failed to capture stdout of `{}`", cmd))?;
+
+    let stderr = child.stderr.take().map(|mut err| {
+        std::thread::spawn(move || {
+            let mut stderr = String::new();
+            let _ = err.read_to_string(&mut stderr);
+            stderr
+        })
+    });
+
+    Ok(LogStream {
+        child,
+        stdout: BufReader::new(stdout),
+        stderr,
+        path: path.to_path_buf(),
+        finished: false,
+    })
+}
+
+impl LogStream {
+    /// Called at the end of stdout, fails if git exited with an error
+    fn finish(&mut self) -> std::io::Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+
+        let status = self.child.wait()?;
+        let stderr = self
+            .stderr
+            .take()
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default();
+
+        if status.success() {
+            return Ok(());
+        }
+
+        if stderr.contains("does not have any commits yet") {
+            return Ok(());
+        }
+
+        Err(std::io::Error::other(format!(
+            "Failed to run command `git log` in `{}`: {:?}",
+            self.path.display(),
+            stderr
+        )))
+    }
+}
+
+impl Read for LogStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.stdout.read(buf)?;
+        if read == 0 && !buf.is_empty() {
+            self.finish()?;
+        }
+
+        Ok(read)
+    }
+}
+
+impl BufRead for LogStream {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.stdout.fill_buf()?.is_empty() {
+            self.finish()?;
+        }
+
+        self.stdout.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.stdout.consume(amt)
+    }
+}
+
+impl Drop for LogStream {
+    fn drop(&mut self) {
+        // Do not leave a zombie process if the stream is not fully read
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +349,60 @@ mod tests {
         // Should return error
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_stream_log_on_empty_git_folder() {
+        let temp_dir = tempdir().unwrap();
+        let temp_dir_path = temp_dir.path().to_path_buf();
+
+        // Init empty git local repo
+        Command::new("git")
+            .arg("init")
+            .arg(&temp_dir_path)
+            .output()
+            .unwrap();
+
+        // Read the whole stream
+        let mut output = String::new();
+        stream_log(&temp_dir_path, &LogOptions::default())
+            .unwrap()
+            .read_to_string(&mut output)
+            .unwrap();
+
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_stream_log_on_invalid_git_folder() {
+        let temp_dir = tempdir().unwrap();
+        let temp_dir_path = temp_dir.path().to_path_buf();
+
+        // Git fails when stdout is finished
+        let mut output = String::new();
+        let result = stream_log(&temp_dir_path, &LogOptions::default())
+            .unwrap()
+            .read_to_string(&mut output);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_log_options_args() {
+        assert_eq!(LogOptions::default().args(), vec!["--all"]);
+
+        let options = LogOptions {
+            revisions: vec!["main".to_string(), "v1.0..v2.0".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(options.args(), vec!["main", "v1.0..v2.0", "--"]);
+
+        let options = LogOptions {
+            merges: Merges::Only,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.args(),
+            vec!["--all", "--merges", "--diff-merges=first-parent"]
+        );
+    }
 }
@@ -1,8 +1,25 @@
-mod git;
+mod conventional;
+mod date;
+mod discover;
+mod error;
+pub mod git;
+mod mailmap;
+#[cfg(feature = "native")]
+mod native;
 mod numstat;
 mod parse_from_path;
+mod parse_from_reader;
 mod parse_from_str;
+#[cfg(test)]
+mod test_utils;
 
+pub use conventional::ConventionalCommit;
+pub use date::parse_date;
+pub use discover::{discover, is_repo};
+pub use error::{Error, Mode, Parsed};
+pub use git::{LogOptions, Merges};
+pub use mailmap::Mailmap;
 pub use numstat::Numstat;
-pub use parse_from_path::parse_from_path;
-pub use parse_from_str::parse_from_str;
+pub use parse_from_path::{parse_from_path, parse_from_path_with};
+pub use parse_from_reader::{parse_from_reader, NumstatIter};
+pub use parse_from_str::{parse_from_str, parse_from_str_with};
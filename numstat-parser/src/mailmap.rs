@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+use crate::numstat::{Author, Numstat};
+
+lazy_static! {
+    static ref ENTRY_RE: Regex = Regex::new(
+        r"^(?P<proper_name>[^<]*)\s*(<(?P<proper_email>[^>]*)>)?\s*(?P<commit_name>[^<]*?)\s*(<(?P<commit_email>[^>]*)>)?$"
+    )
+    .unwrap();
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    proper_name: Option<String>,
+    proper_email: Option<String>,
+    commit_name: Option<String>,
+    commit_email: String,
+}
+
+/// Canonical identities from a `.mailmap` file, see `git help mailmap`
+///
+/// ```txt
+/// Proper Name <commit@email.xx>
+/// <proper@email.xx> <commit@email.xx>
+/// Proper Name <proper@email.xx> <commit@email.xx>
+/// Proper Name <proper@email.xx> Commit Name <commit@email.xx>
+/// ```
+#[derive(Debug, Default)]
+pub struct Mailmap {
+    entries: Vec<Entry>,
+}
+
+impl Mailmap {
+    pub fn parse(s: &str) -> Self {
+        let entries = s
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| {
+                let captures = ENTRY_RE.captures(line)?;
+                let get = |name: &str| {
+                    captures
+                        .name(name)
+                        .map(|m| m.as_str().trim().to_string())
+                        .filter(|s| !s.is_empty())
+                };
+
+                // With a single email, it is the commit email
+                match (get("proper_email"), get("commit_email")) {
+                    (Some(proper_email), Some(commit_email)) => Some(Entry {
+                        proper_name: get("proper_name"),
+                        proper_email: Some(proper_email),
+                        commit_name: get("commit_name"),
+                        commit_email,
+                    }),
+                    (Some(commit_email), None) => Some(Entry {
+                        proper_name: get("proper_name"),
+                        commit_email,
+                        ..Default::default()
+                    }),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Reading mailmap `{}`", path.display()))?;
+
+        Ok(Self::parse(&content))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add the entries of another mailmap, e.g. from multiple files
+    pub fn extend(&mut self, other: Mailmap) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Canonical identity, entries matching both name and email take precedence
+    pub fn canonicalize(&self, author: &Author) -> Author {
+        let email_matches = |e: &&Entry| e.commit_email.eq_ignore_ascii_case(&author.email);
+
+        let entry = self
+            .entries
+            .iter()
+            .filter(email_matches)
+            .find(|e| {
+                e.commit_name
+                    .as_ref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(&author.name))
+            })
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .filter(email_matches)
+                    .find(|e| e.commit_name.is_none())
+            });
+
+        match entry {
+            Some(entry) => {
+                let name = entry.proper_name.as_ref().unwrap_or(&author.name).clone();
+                let email = entry.proper_email.as_ref().unwrap_or(&author.email).clone();
+
+                Author {
+                    full: format!("{} <{}>", name, email),
+                    name,
+                    email,
+                }
+            }
+            None => author.clone(),
+        }
+    }
+
+    /// Canonicalize the author, committer and co-authors of a commit
+    pub fn apply(&self, numstat: &mut Numstat) {
+        numstat.author = self.canonicalize(&numstat.author);
+        numstat.committer = self.canonicalize(&numstat.committer);
+        for co_author in numstat.co_authors.iter_mut() {
+            *co_author = self.canonicalize(co_author);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn author(name: &str, email: &str) -> Author {
+        Author {
+            full: format!("{} <{}>", name, email),
+            name: name.to_string(),
+            email: email.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mailmap() {
+        let mailmap = Mailmap::parse(indoc! {"
+            # Comment
+            Duyet Le <5009534+duyet@users.noreply.github.com>
+            <me@duyet.net> <lvduit08@gmail.com>
+            Duyet Le <me@duyet.net> <lvduyet@fossil.com> # inline comment
+            Duyet Bot <bot@duyet.net> duyetbot <me@duyet.net>
+        "});
+
+        // Proper name only
+        let out = mailmap.canonicalize(&author("duyet", "5009534+duyet@users.noreply.github.com"));
+        assert_eq!(out.name, "Duyet Le");
+        assert_eq!(out.email, "5009534+duyet@users.noreply.github.com");
+
+        // Proper email only
+        let out = mailmap.canonicalize(&author("Duyet", "LVDUIT08@gmail.com"));
+        assert_eq!(out.name, "Duyet");
+        assert_eq!(out.email, "me@duyet.net");
+
+        // Proper name and email
+        let out = mailmap.canonicalize(&author("Duyet", "lvduyet@fossil.com"));
+        assert_eq!(out.full, "Duyet Le <me@duyet.net>");
+
+        // Matching by commit name and email
+        let out = mailmap.canonicalize(&author("duyetbot", "me@duyet.net"));
+        assert_eq!(out.full, "Duyet Bot <bot@duyet.net>");
+
+        // Not in the mailmap
+        let out = mailmap.canonicalize(&author("Duyet Le", "me@duyet.net"));
+        assert_eq!(out.full, "Duyet Le <me@duyet.net>");
+    }
+}
@@ -0,0 +1,422 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use git2::{Commit, DiffFindOptions, Oid, Patch, Repository, Revwalk, Signature, Sort};
+use log::debug;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::error::{Error, Mode, Parsed};
+use crate::git::{LogOptions, Merges};
+use crate::numstat::{Author, Numstat};
+use crate::parse_from_str::{format_rename, new_stat, parse_co_author};
+
+/// Tags and branches pointing to each commit
+type Decorations = HashMap<Oid, (Vec<String>, Vec<String>)>;
+
+/// Same as `git log --all --numstat` but reading the repository in-process,
+/// the commits that cannot be read are skipped in [`Mode::Lenient`].
+/// Remote repositories are still cloned with the `git` binary.
+pub fn get_numstats(path: &PathBuf, options: &LogOptions, mode: Mode) -> Result<Parsed> {
+    debug!("Opening {} with libgit2", path.display());
+
+    let repo = Repository::open(path)
+        .with_context(|| format!("Failed to open git repository `{}`", path.display()))?;
+
+    let mut revwalk = repo.revwalk()?;
+    // Topological so that the children are seen before their parents, see `Sources`
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+    if options.revisions.is_empty() {
+        revwalk.push_glob("*")?;
+
+        // Repository does not have any commits yet
+        if repo.head().is_ok() {
+            revwalk.push_head()?;
+        }
+    } else {
+        for revision in &options.revisions {
+            push(&repo, &mut revwalk, revision)?;
+        }
+    }
+
+    let decorations = decorations(&repo)?;
+    let mut sources = Sources::new(&repo, options)?;
+
+    // Same as `git log --since/--until`, using the commit date
+    let in_range = |commit: &Commit| {
+        let time = commit.time().seconds();
+        options.since.is_none_or(|since| time >= since.timestamp())
+            && options.until.is_none_or(|until| time <= until.timestamp())
+            && options.merges.matches(commit.parent_count() > 1)
+    };
+
+    let results = revwalk.map(|oid| {
+        let oid = oid.map_err(|e| Error::Commit {
+            commit: String::new(),
+            message: e.message().to_string(),
+        })?;
+        let commit = repo.find_commit(oid).map_err(|e| commit_error(oid, e))?;
+
+        // Every walked commit passes its source on, even the filtered out ones
+        let source = sources.visit(&commit);
+
+        if !in_range(&commit) {
+            return Ok(None);
+        }
+
+        to_numstat(&repo, &commit, &decorations, source, options)
+            .map(Some)
+            .map_err(|e| commit_error(oid, e))
+    });
+
+    Ok(Parsed::collect(
+        results.filter_map(Result::transpose),
+        mode,
+    )?)
+}
+
+fn commit_error(oid: Oid, e: impl std::fmt::Display) -> Error {
+    Error::Commit {
+        commit: oid.to_string(),
+        message: e.to_string(),
+    }
+}
+
+fn to_numstat(
+    repo: &Repository,
+    commit: &Commit,
+    decorations: &Decorations,
+    source: String,
+    options: &LogOptions,
+) -> Result<Numstat> {
+    let (author, date) = identity(&commit.author())?;
+    let (committer, commit_date) = identity(&commit.committer())?;
+
+    let merges = if commit.parent_count() > 1 {
+        commit
+            .parent_ids()
+            .map(|id| id.to_string()[..7].to_string())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let message = commit
+        .message()
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| format!("{}\n", l))
+        .collect();
+
+    let co_authors = commit
+        .message()
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| parse_co_author(l.trim()))
+        .collect();
+
+    let (tags, branches) = decorations.get(&commit.id()).cloned().unwrap_or_default();
+
+    Ok(Numstat {
+        // Set by the caller, which knows where the repository was found
+        repo: String::new(),
+        commit: commit.id().to_string(),
+        parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+        merges,
+        author,
+        co_authors,
+        date,
+        committer,
+        commit_date,
+        branches,
+        source,
+        tags,
+        message,
+        stats: stats(repo, commit, options.merges)?,
+    })
+}
+
+fn identity(signature: &Signature) -> Result<(Author, DateTime<FixedOffset>)> {
+    let name = signature.name().unwrap_or("Unknown").to_string();
+    let email = signature
+        .email()
+        .unwrap_or("unknown@example.com")
+        .to_string();
+
+    let offset = FixedOffset::east_opt(signature.when().offset_minutes() * 60)
+        .context("Invalid timezone offset")?;
+    let date = DateTime::from_timestamp(signature.when().seconds(), 0)
+        .context("Invalid commit timestamp")?
+        .with_timezone(&offset);
+
+    let author = Author {
+        full: format!("{} <{}>", name, email),
+        name,
+        email,
+    };
+
+    Ok((author, date))
+}
+
+fn stats(repo: &Repository, commit: &Commit, merges: Merges) -> Result<Vec<crate::numstat::Stat>> {
+    // Like `git log --numstat`, merge commits have no stats
+    // unless `--diff-merges=first-parent`
+    if commit.parent_count() > 1 && merges != Merges::Only {
+        return Ok(vec![]);
+    }
+
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+
+    let mut diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let mut stats = vec![];
+    for idx in 0..diff.deltas().len() {
+        let patch = Patch::from_diff(&diff, idx)?;
+        let delta = match diff.get_delta(idx) {
+            Some(delta) => delta,
+            None => continue,
+        };
+
+        let old_path = delta.old_file().path().map(|p| p.to_string_lossy());
+        let new_path = delta.new_file().path().map(|p| p.to_string_lossy());
+        let path = match (old_path, new_path) {
+            (Some(old), Some(new)) if old != new => format_rename(&old, &new),
+            (_, Some(new)) => new.to_string(),
+            (Some(old), None) => old.to_string(),
+            (None, None) => continue,
+        };
+
+        let stat = match patch {
+            Some(patch) if !patch.delta().flags().is_binary() => {
+                let (_, added, deleted) = patch.line_stats()?;
+                new_stat(added as u32, deleted as u32, path, false)
+            }
+            _ => new_stat(0, 0, path, true),
+        };
+
+        stats.push(stat);
+    }
+
+    Ok(stats)
+}
+
+/// Same as the decorations of `git log`, e.g. `(tag: v1, origin/master)`
+fn decorations(repo: &Repository) -> Result<Decorations> {
+    let mut out = Decorations::new();
+
+    for reference in repo.references()?.flatten() {
+        let name = match reference.shorthand() {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let oid = match reference.peel_to_commit() {
+            Ok(commit) => commit.id(),
+            Err(_) => continue,
+        };
+
+        let entry = out.entry(oid).or_default();
+        if reference.is_tag() {
+            entry.0.push(name);
+        } else if reference.is_branch() || reference.is_remote() {
+            entry.1.push(name);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Push a revision like `main` or a range like `v1.0..v2.0`
+fn push(repo: &Repository, revwalk: &mut Revwalk, revision: &str) -> Result<()> {
+    if revision.contains("..") {
+        revwalk.push_range(revision)?;
+    } else {
+        let oid = repo
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Invalid revision `{}`", revision))?
+            .id();
+        revwalk.push(oid)?;
+    }
+
+    Ok(())
+}
+
+/// Same as `git log --source`, each commit is attributed to the first ref reaching it:
+/// HEAD, then the branches, remotes and tags, or the given revisions.
+/// Built during the walk: a commit passes its ref on to its parents,
+/// which are only visited once all of their children have been.
+struct Sources {
+    names: Vec<String>,
+    /// Index in `names` of the first ref reaching each commit seen so far
+    reached: HashMap<Oid, usize>,
+}
+
+impl Sources {
+    fn new(repo: &Repository, options: &LogOptions) -> Result<Self> {
+        let mut names = vec![];
+        if options.revisions.is_empty() {
+            if let Ok(head) = repo.head() {
+                names.push(head.name().unwrap_or("HEAD").to_string());
+            }
+            for reference in repo.references()?.flatten() {
+                if let Some(name) = reference.name() {
+                    names.push(name.to_string());
+                }
+            }
+        } else {
+            names.extend(options.revisions.iter().cloned());
+        }
+
+        let mut reached = HashMap::new();
+        for (i, name) in names.iter().enumerate() {
+            // The end of a range is where its walk starts
+            let tip = name.rsplit("..").next().unwrap_or(name);
+            if let Ok(commit) = repo.revparse_single(tip).and_then(|o| o.peel_to_commit()) {
+                reached.entry(commit.id()).or_insert(i);
+            }
+        }
+
+        Ok(Self { names, reached })
+    }
+
+    /// Source of the commit, passed on to its parents
+    fn visit(&mut self, commit: &Commit) -> String {
+        let i = match self.reached.get(&commit.id()) {
+            Some(&i) => i,
+            None => return String::new(),
+        };
+
+        for parent in commit.parent_ids() {
+            let entry = self.reached.entry(parent).or_insert(i);
+            *entry = (*entry).min(i);
+        }
+
+        self.names[i].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_native_same_as_git_log() {
+        let temp_dir = tempdir().unwrap();
+        let temp_dir_path = temp_dir.path().to_path_buf();
+
+        let git = |args: &[&str]| crate::test_utils::git(&temp_dir_path, args);
+
+        git(&["init"]);
+        std::fs::write(temp_dir_path.join("a.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(temp_dir_path.join("logo.png"), [0u8, 159, 146, 150]).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "feat: init"]);
+        git(&["tag", "v1"]);
+        std::fs::write(temp_dir_path.join("a.rs"), "fn main() {\n}\n").unwrap();
+        git(&["commit", "-am", "fix: format"]);
+        std::fs::create_dir_all(temp_dir_path.join("src/utils")).unwrap();
+        std::fs::write(temp_dir_path.join("src/git.rs"), "fn git() {}\n".repeat(10)).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "feat: git"]);
+        git(&["mv", "src/git.rs", "src/utils/git.rs"]);
+        git(&["commit", "-m", "refactor: move git"]);
+
+        let mut native = get_numstats(&temp_dir_path, &LogOptions::default(), Mode::Strict)
+            .unwrap()
+            .numstats;
+        let mut text =
+            crate::parse_from_str(&crate::git::get_log(&temp_dir_path).unwrap()).unwrap();
+
+        // The commits of the same second are not in the same order
+        native.sort_by(|a, b| a.commit.cmp(&b.commit));
+        text.sort_by(|a, b| a.commit.cmp(&b.commit));
+
+        assert_eq!(native.len(), 4);
+        assert_eq!(native.len(), text.len());
+
+        for (a, b) in native.iter().zip(text.iter()) {
+            assert_eq!(a.commit, b.commit);
+            assert_eq!(a.parents, b.parents);
+            assert_eq!(a.author.name, b.author.name);
+            assert_eq!(a.author.email, b.author.email);
+            assert_eq!(a.date, b.date);
+            assert_eq!(a.committer.email, b.committer.email);
+            assert_eq!(a.commit_date, b.commit_date);
+            assert_eq!(a.message, b.message);
+            assert_eq!(a.tags, b.tags);
+            assert_eq!(a.stats.len(), b.stats.len());
+
+            for (x, y) in a.stats.iter().zip(b.stats.iter()) {
+                assert_eq!(x.path, y.path);
+                assert_eq!(x.added, y.added);
+                assert_eq!(x.deleted, y.deleted);
+                assert_eq!(x.is_binary, y.is_binary);
+            }
+        }
+
+        let find = |message: &str| native.iter().find(|n| n.message == message).unwrap();
+        assert_eq!(
+            find("refactor: move git\n").stats[0].path,
+            "src/{ => utils}/git.rs"
+        );
+        assert_eq!(find("feat: init\n").tags, vec!["v1"]);
+    }
+
+    #[test]
+    fn test_native_sources() {
+        let temp_dir = tempdir().unwrap();
+        let temp_dir_path = temp_dir.path().to_path_buf();
+
+        let git = |args: &[&str]| crate::test_utils::git(&temp_dir_path, args);
+
+        git(&["init", "-b", "main"]);
+        git(&["commit", "--allow-empty", "-m", "a"]);
+        git(&["checkout", "-b", "feature"]);
+        git(&["commit", "--allow-empty", "-m", "b"]);
+        git(&["checkout", "main"]);
+        git(&["commit", "--allow-empty", "-m", "c"]);
+
+        let native = get_numstats(&temp_dir_path, &LogOptions::default(), Mode::Strict)
+            .unwrap()
+            .numstats;
+
+        let sources = |numstats: &[Numstat]| {
+            let mut sources = numstats
+                .iter()
+                .map(|n| (n.message.clone(), n.source.clone()))
+                .collect::<Vec<_>>();
+            sources.sort();
+            sources
+        };
+
+        // HEAD first, even if `git log` walks the feature branch first
+        assert_eq!(
+            sources(&native),
+            vec![
+                ("a\n".to_string(), "refs/heads/main".to_string()),
+                ("b\n".to_string(), "refs/heads/feature".to_string()),
+                ("c\n".to_string(), "refs/heads/main".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_native_on_invalid_git_folder() {
+        let temp_dir = tempdir().unwrap();
+
+        let result = get_numstats(
+            &temp_dir.path().to_path_buf(),
+            &LogOptions::default(),
+            Mode::Strict,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use chrono::{DateTime, FixedOffset};
 
-#[derive(Debug, Default)]
+use crate::conventional::ConventionalCommit;
+
+#[derive(Debug, Default, Clone)]
 pub struct Author {
     pub full: String,
     pub name: String,
@@ -11,18 +13,47 @@ pub struct Author {
 pub struct Stat {
     pub added: u32,
     pub deleted: u32,
+    /// Path as printed by git, e.g. `src/{old.rs => new.rs}`
     pub path: String,
+    /// Path before the rename, same as `new_path` otherwise
+    pub old_path: String,
+    /// Path after the rename, same as `old_path` otherwise
+    pub new_path: String,
     pub extension: String,
+    /// Binary file, `added` and `deleted` are always 0
+    pub is_binary: bool,
+    /// Renamed or copied file
+    pub is_rename: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct Numstat {
+    /// Repository the commit was read from, relative to the scanned folder
+    pub repo: String,
     pub commit: String,
+    /// Full hashes of the parents, only with `git log --parents` or the native backend
+    pub parents: Vec<String>,
     pub merges: Vec<String>,
     pub author: Author,
+    /// From the `Co-authored-by: Name <email>` trailers
+    pub co_authors: Vec<Author>,
+    /// Author date
     pub date: DateTime<FixedOffset>,
+    /// Same as `author` unless the commit was rebased, cherry-picked or applied
+    pub committer: Author,
+    pub commit_date: DateTime<FixedOffset>,
+    /// Branches pointing to the commit
     pub branches: Vec<String>,
+    /// Ref the commit was reached from, like `git log --source`, e.g. `refs/heads/main`
+    pub source: String,
     pub tags: Vec<String>,
     pub message: String,
     pub stats: Vec<Stat>,
 }
+
+impl Numstat {
+    /// Type, scope and breaking flag of a conventional commit message
+    pub fn conventional(&self) -> Option<ConventionalCommit> {
+        ConventionalCommit::parse(&self.message)
+    }
+}
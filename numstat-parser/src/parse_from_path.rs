@@ -1,10 +1,14 @@
 use anyhow::{bail, Result};
 use log::debug;
 use rayon::prelude::*;
-use std::path::PathBuf;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 
-use crate::git;
-use crate::parse_from_str;
+use crate::discover::{discover, is_repo};
+use crate::error::{Error, Mode, Parsed};
+use crate::git::{self, LogOptions};
+use crate::mailmap::Mailmap;
+use crate::parse_from_reader;
 
 // BUG-023: Helper function for enhanced Git URL validation
 fn is_valid_git_url(url: &str) -> bool {
@@ -15,58 +19,92 @@ fn is_valid_git_url(url: &str) -> bool {
         || url.ends_with(".git")
 }
 
+/// Read the history of a git working directory, honoring its `.mailmap`
+fn parse_git_dir(path: &PathBuf, mode: Mode, options: &LogOptions) -> Result<Parsed> {
+    let mut parsed = read_git_dir(path, mode, options)?;
+
+    let repo = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    set_repo(&mut parsed, &repo);
+
+    let mailmap = path.join(".mailmap");
+    if mailmap.is_file() {
+        let mailmap = Mailmap::from_file(&mailmap)?;
+        debug!("Applying {}/.mailmap", path.display());
+        parsed.numstats.iter_mut().for_each(|n| mailmap.apply(n));
+    }
+
+    Ok(parsed)
+}
+
+/// Read the history of a git working directory using `git log`
+#[cfg(not(feature = "native"))]
+fn read_git_dir(path: &PathBuf, mode: Mode, options: &LogOptions) -> Result<Parsed> {
+    // Parse while git is running, without buffering the whole log
+    Ok(Parsed::collect(
+        parse_from_reader(git::stream_log(path, options)?),
+        mode,
+    )?)
+}
+
+/// Read the history of a git working directory in-process
+#[cfg(feature = "native")]
+fn read_git_dir(path: &PathBuf, mode: Mode, options: &LogOptions) -> Result<Parsed> {
+    crate::native::get_numstats(path, options, mode)
+}
+
 pub fn parse_from_path(paths: &[PathBuf]) -> Result<Vec<crate::Numstat>> {
+    Ok(parse_from_path_with(paths, Mode::Lenient, &LogOptions::default())?.numstats)
+}
+
+/// Parse from paths, see [`Mode`] for the handling of invalid commits and paths.
+/// With multiple paths or a folder of git repositories, the failed paths are
+/// reported as [`Error::Path`] diagnostics in [`Mode::Lenient`].
+/// The `options` are passed to `git log` when reading git repositories.
+/// The whole history is kept in memory, use [`crate::parse_from_reader`]
+/// on [`git::stream_log`] to handle the commits while git is running.
+pub fn parse_from_path_with(paths: &[PathBuf], mode: Mode, options: &LogOptions) -> Result<Parsed> {
     if paths.len() == 1 {
         let path = &paths[0];
 
         match path {
             path if path.is_dir() => {
-                let dirs = if path.join(".git").exists() {
-                    vec![git::get_log(path)?]
-                } else {
-                    // BUG-020: Safely iterate directory entries
-                    let git_dirs = std::fs::read_dir(path)?
-                        .filter_map(|entry| {
-                            entry.ok().and_then(|e| {
-                                let path = e.path();
-                                if path.join(".git").exists() {
-                                    Some(path)
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                        .collect::<Vec<_>>();
-
-                    git_dirs
-                        .par_iter()
-                        .filter_map(|entry| {
-                            debug!("running git log on {}", entry.display());
-                            git::get_log(entry).ok()
-                        })
-                        .collect()
-                };
-
-                debug!(
-                    "Scanning `{}`, found {} git dir(s)",
-                    path.display(),
-                    dirs.len()
-                );
-
-                if dirs.is_empty() {
+                if is_repo(path) {
+                    return parse_git_dir(path, mode, options);
+                }
+
+                let git_dirs = discover(path, options.max_depth, &options.exclude)?;
+                if git_dirs.is_empty() {
                     bail!("No .git found");
                 }
 
-                let gitlogs = dirs.join("\n");
-                debug!("Number of gitlog lines: {}", gitlogs.lines().count());
+                let results = git_dirs
+                    .par_iter()
+                    .map(|entry| {
+                        debug!("reading git history of {}", entry.display());
+                        let mut parsed = parse_git_dir(entry, mode, options)
+                            .map_err(|e| path_error(entry, e))?;
+
+                        // Tell apart the repositories with the same name in different folders
+                        let repo = entry.strip_prefix(path).unwrap_or(entry);
+                        set_repo(&mut parsed, &repo.to_string_lossy());
 
-                parse_from_str(&gitlogs)
+                        Ok(parsed)
+                    })
+                    .collect::<Vec<_>>();
+
+                merge(results, mode)
             }
 
             path if path.is_file() => {
                 // Parse the file, if the argument is a path to a numstat.txt file
-                let output = std::fs::read_to_string(path)?;
-                parse_from_str(&output)
+                let file = std::fs::File::open(path)?;
+                Ok(Parsed::collect(
+                    parse_from_reader(BufReader::new(file)),
+                    mode,
+                )?)
             }
 
             path if is_valid_git_url(&path.to_string_lossy()) => {
@@ -81,7 +119,14 @@ pub fn parse_from_path(paths: &[PathBuf]) -> Result<Vec<crate::Numstat>> {
                 // Run git clone
                 git::clone(&url, &temp_dir_path)?;
 
-                parse_from_path(&[temp_dir_path])
+                let mut parsed = parse_from_path_with(&[temp_dir_path], mode, options)?;
+
+                // Name of the repository instead of the tempdir
+                let repo = url.trim_end_matches('/').trim_end_matches(".git");
+                let repo = repo.rsplit(['/', ':']).next().unwrap_or(repo);
+                set_repo(&mut parsed, repo);
+
+                Ok(parsed)
             }
 
             invalid_path => {
@@ -89,20 +134,52 @@ pub fn parse_from_path(paths: &[PathBuf]) -> Result<Vec<crate::Numstat>> {
             }
         }
     } else {
-        Ok(paths
+        let results = paths
             .par_iter()
-            .flat_map(|path| {
-                parse_from_path(&[path.to_path_buf()])
-                    .ok()
-                    .unwrap_or_default()
+            .map(|path| {
+                parse_from_path_with(&[path.to_path_buf()], mode, options)
+                    .map_err(|e| path_error(path, e))
             })
-            .collect::<Vec<crate::Numstat>>())
+            .collect::<Vec<_>>();
+
+        merge(results, mode)
     }
 }
 
+fn set_repo(parsed: &mut Parsed, repo: &str) {
+    parsed
+        .numstats
+        .iter_mut()
+        .for_each(|n| n.repo = repo.to_string());
+}
+
+fn path_error(path: &Path, e: anyhow::Error) -> Error {
+    Error::Path {
+        path: path.to_path_buf(),
+        message: format!("{:#}", e),
+    }
+}
+
+/// Merge the results of multiple paths, the failed paths are fatal in [`Mode::Strict`]
+fn merge(results: Vec<Result<Parsed, Error>>, mode: Mode) -> Result<Parsed> {
+    let mut parsed = Parsed::default();
+
+    for result in results {
+        match result {
+            Ok(other) => parsed.extend(other),
+            Err(e) if mode == Mode::Strict => return Err(e.into()),
+            Err(e) => parsed.diagnostics.push(e),
+        }
+    }
+
+    Ok(parsed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::Merges;
+    use crate::test_utils::{git, git_with_env};
     use std::path::Path;
     use tempfile::tempdir;
 
@@ -198,4 +275,207 @@ mod tests {
         // Error message
         assert_eq!(out.unwrap_err().to_string(), "No .git found".to_string());
     }
+
+    #[test]
+    fn parse_from_multiple_paths_with_invalid_path() {
+        let paths = [
+            Path::new("tests/sample.txt").to_path_buf(),
+            Path::new("tests/not_found.txt").to_path_buf(),
+        ];
+
+        // Lenient: the invalid path is reported as a diagnostic
+        let parsed = parse_from_path_with(&paths, Mode::Lenient, &LogOptions::default()).unwrap();
+        assert_eq!(parsed.numstats.len(), 4);
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(
+            parsed.diagnostics[0].to_string(),
+            "tests/not_found.txt: Invalid path: tests/not_found.txt"
+        );
+
+        // Strict: fail on the invalid path
+        let out = parse_from_path_with(&paths, Mode::Strict, &LogOptions::default());
+        assert!(out.is_err());
+    }
+
+    #[test]
+    fn parse_from_git_dir_with_since_until() {
+        let temp_dir = tempdir().unwrap();
+        let temp_dir_path = temp_dir.path().to_path_buf();
+
+        let commit = |date: &str, file: &str| {
+            std::fs::write(temp_dir_path.join(file), date).unwrap();
+            git(&temp_dir_path, &["add", "."]);
+            git_with_env(
+                &temp_dir_path,
+                &["commit", "-m", file],
+                &[("GIT_AUTHOR_DATE", date), ("GIT_COMMITTER_DATE", date)],
+            );
+        };
+
+        git(&temp_dir_path, &["init"]);
+        commit("2022-06-01T00:00:00+00:00", "a.txt");
+        commit("2023-02-01T00:00:00+00:00", "b.txt");
+        commit("2023-06-01T00:00:00+00:00", "c.txt");
+
+        let options = LogOptions {
+            since: Some(crate::parse_date("2023-01-01").unwrap()),
+            until: Some(crate::parse_date("2023-03-01").unwrap()),
+            ..Default::default()
+        };
+        let parsed = parse_from_path_with(&[temp_dir_path], Mode::Strict, &options).unwrap();
+
+        assert_eq!(parsed.numstats.len(), 1);
+        assert_eq!(parsed.numstats[0].stats[0].path, "b.txt");
+    }
+
+    #[test]
+    fn parse_from_git_dir_with_revisions() {
+        let temp_dir = tempdir().unwrap();
+        let temp_dir_path = temp_dir.path().to_path_buf();
+
+        let git = |args: &[&str]| git(&temp_dir_path, args);
+
+        git(&["init", "-b", "main"]);
+        std::fs::write(temp_dir_path.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "a"]);
+        git(&["checkout", "-b", "feature"]);
+        std::fs::write(temp_dir_path.join("b.txt"), "b").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "b"]);
+        git(&["checkout", "main"]);
+
+        // All the branches, with the ref reaching each commit
+        let parsed = parse_from_path_with(
+            std::slice::from_ref(&temp_dir_path),
+            Mode::Strict,
+            &LogOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(parsed.numstats.len(), 2);
+        let source = |path: &str| {
+            parsed
+                .numstats
+                .iter()
+                .find(|n| n.stats[0].path == path)
+                .map(|n| n.source.clone())
+                .unwrap()
+        };
+        assert_eq!(source("a.txt"), "refs/heads/main");
+        assert_eq!(source("b.txt"), "refs/heads/feature");
+
+        // Only the main branch
+        let options = LogOptions {
+            revisions: vec!["main".to_string()],
+            ..Default::default()
+        };
+        let parsed =
+            parse_from_path_with(std::slice::from_ref(&temp_dir_path), Mode::Strict, &options)
+                .unwrap();
+        assert_eq!(parsed.numstats.len(), 1);
+        assert_eq!(parsed.numstats[0].stats[0].path, "a.txt");
+
+        // Revision range
+        let options = LogOptions {
+            revisions: vec!["main..feature".to_string()],
+            ..Default::default()
+        };
+        let parsed = parse_from_path_with(&[temp_dir_path], Mode::Strict, &options).unwrap();
+        assert_eq!(parsed.numstats.len(), 1);
+        assert_eq!(parsed.numstats[0].stats[0].path, "b.txt");
+    }
+
+    #[test]
+    fn parse_from_git_dir_with_merges() {
+        let temp_dir = tempdir().unwrap();
+        let temp_dir_path = temp_dir.path().to_path_buf();
+
+        let git = |args: &[&str]| git(&temp_dir_path, args);
+
+        git(&["init", "-b", "main"]);
+        std::fs::write(temp_dir_path.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "a"]);
+        git(&["checkout", "-b", "feature"]);
+        std::fs::write(temp_dir_path.join("b.txt"), "b").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "b"]);
+        git(&["checkout", "main"]);
+        git(&[
+            "merge",
+            "--no-ff",
+            "-m",
+            "Merge pull request #1 from duyet/feature",
+            "feature",
+        ]);
+
+        let parse = |merges: Merges| {
+            let options = LogOptions {
+                merges,
+                ..Default::default()
+            };
+            parse_from_path_with(std::slice::from_ref(&temp_dir_path), Mode::Strict, &options)
+                .unwrap()
+                .numstats
+        };
+
+        let numstats = parse(Merges::Include);
+        assert_eq!(numstats.len(), 3);
+
+        let numstats = parse(Merges::Exclude);
+        assert_eq!(numstats.len(), 2);
+        assert!(numstats.iter().all(|n| n.merges.is_empty()));
+
+        // The merge with the stats against the first parent
+        let numstats = parse(Merges::Only);
+        assert_eq!(numstats.len(), 1);
+        assert_eq!(numstats[0].merges.len(), 2);
+        assert_eq!(numstats[0].stats.len(), 1);
+        assert_eq!(numstats[0].stats[0].path, "b.txt");
+    }
+
+    #[test]
+    fn parse_from_nested_git_dirs() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+
+        let init = |dir: &str| {
+            let path = root.join(dir);
+            std::fs::create_dir_all(&path).unwrap();
+            git(&path, &["init"]);
+            std::fs::write(path.join("a.txt"), "a").unwrap();
+            git(&path, &["add", "."]);
+            git(&path, &["commit", "-m", "a"]);
+        };
+
+        init("org/api");
+        init("org/team/api");
+        init("archive/old");
+
+        let parse = |max_depth: usize, exclude: &[&str]| {
+            let options = LogOptions {
+                max_depth,
+                exclude: exclude.iter().map(|e| e.to_string()).collect(),
+                ..Default::default()
+            };
+            let mut repos =
+                parse_from_path_with(std::slice::from_ref(&root), Mode::Strict, &options)
+                    .map(|p| p.numstats.into_iter().map(|n| n.repo).collect::<Vec<_>>())
+                    .unwrap_or_default();
+            repos.sort();
+            repos
+        };
+
+        // The default only looks at the direct children
+        assert!(parse(1, &[]).is_empty());
+        assert_eq!(parse(2, &[]), vec!["archive/old", "org/api"]);
+        assert_eq!(parse(3, &["archive"]), vec!["org/api", "org/team/api"]);
+
+        // A repository is named after its folder when given directly
+        let options = LogOptions::default();
+        let numstats = parse_from_path_with(&[root.join("org/api")], Mode::Strict, &options)
+            .unwrap()
+            .numstats;
+        assert_eq!(numstats[0].repo, "api");
+    }
 }
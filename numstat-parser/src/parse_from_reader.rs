@@ -0,0 +1,161 @@
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::parse_from_str::parse_block;
+
+/// Parse git log --numstat content lazily, one commit at a time
+///
+/// Only the current commit block is kept in memory,
+/// so this can be used on the output of `git log` while it is still running.
+/// The `girs` CLI does not, it collects the whole history before its queries.
+///
+/// ```no_run
+/// use std::io::BufReader;
+///
+/// let file = std::fs::File::open("numstat.txt").unwrap();
+/// for numstat in numstat_parser::parse_from_reader(BufReader::new(file)) {
+///     println!("{}", numstat.unwrap().commit);
+/// }
+/// ```
+pub fn parse_from_reader<R: BufRead>(reader: R) -> NumstatIter<R> {
+    NumstatIter {
+        reader,
+        block: String::new(),
+        block_start: 1,
+        line: Vec::new(),
+        line_no: 0,
+        done: false,
+    }
+}
+
+/// Iterator returned by [`parse_from_reader`]
+///
+/// Blocks that cannot be parsed are returned as errors with their line number,
+/// I/O errors end the iteration. Use [`crate::Parsed::collect`] to apply a [`crate::Mode`].
+pub struct NumstatIter<R> {
+    reader: R,
+    block: String,
+    block_start: usize,
+    line: Vec<u8>,
+    line_no: usize,
+    done: bool,
+}
+
+impl<R: BufRead> NumstatIter<R> {
+    /// Take the current block and parse it, `None` if it is empty
+    fn flush(&mut self) -> Option<Result<crate::Numstat, Error>> {
+        let block = std::mem::take(&mut self.block);
+        if block.trim().is_empty() {
+            return None;
+        }
+
+        Some(parse_block(&block, self.block_start))
+    }
+}
+
+impl<R: BufRead> Iterator for NumstatIter<R> {
+    type Item = Result<crate::Numstat, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line.clear();
+
+            let read = match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(read) => read,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            };
+
+            // End of input, parse the last block
+            if read == 0 {
+                self.done = true;
+                return self.flush();
+            }
+
+            self.line_no += 1;
+            let line = String::from_utf8_lossy(&self.line).into_owned();
+
+            // A new commit starts, parse the previous block
+            let numstat = if line.starts_with("commit ") {
+                let numstat = self.flush();
+                self.block_start = self.line_no;
+                numstat
+            } else {
+                None
+            };
+
+            self.block.push_str(&line);
+
+            if numstat.is_some() {
+                return numstat;
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mode, Parsed};
+    use indoc::indoc;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_parse_from_reader() {
+        let file = std::fs::File::open("tests/sample.txt").unwrap();
+        let out = parse_from_reader(BufReader::new(file))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let expected =
+            crate::parse_from_str(&std::fs::read_to_string("tests/sample.txt").unwrap()).unwrap();
+
+        assert_eq!(out.len(), 4);
+        assert_eq!(out.len(), expected.len());
+        for (a, b) in out.iter().zip(expected.iter()) {
+            assert_eq!(a.commit, b.commit);
+            assert_eq!(a.message, b.message);
+            assert_eq!(a.stats.len(), b.stats.len());
+        }
+    }
+
+    #[test]
+    fn test_parse_from_reader_empty() {
+        let out = parse_from_reader("".as_bytes()).collect::<Vec<_>>();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_parse_from_reader_invalid_date() {
+        let raw = indoc! {"
+            commit 1d4171694d9322ab22ee7cdd6712b83ecd8ae6c1
+            Author: Duyet Le <me@duyet.net>
+            Date:   Tue, 10 Jan 2023 00:35:39 +0700
+
+                feat: first
+
+            commit 598070021341c247fa041baac291fa1bfa0133b4
+            Author: Duyet Le <me@duyet.net>
+            Date:   yesterday
+
+                feat: second
+        "};
+
+        // Strict: fail with the line number
+        let err = Parsed::collect(parse_from_reader(raw.as_bytes()), Mode::Strict).unwrap_err();
+        assert_eq!(err.to_string(), "line 9: invalid date `yesterday`");
+
+        // Lenient: keep the valid commit, report the invalid one
+        let parsed = Parsed::collect(parse_from_reader(raw.as_bytes()), Mode::Lenient).unwrap();
+        assert_eq!(parsed.numstats.len(), 1);
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert!(matches!(
+            parsed.diagnostics[0],
+            Error::InvalidDate { line: 9, .. }
+        ));
+    }
+}
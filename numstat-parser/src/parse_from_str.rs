@@ -1,16 +1,23 @@
-use anyhow::{anyhow, Context, Result};
-use chrono::DateTime;
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset};
 use lazy_static::lazy_static;
 use log::debug;
 use rayon::prelude::*;
 use regex::Regex;
 
+use crate::error::{Error, Mode, Parsed};
+
 lazy_static! {
     static ref BRANCH_TAG_RE: Regex = Regex::new(r"\((.+)\)").unwrap();
-    static ref AUTHOR_RE: Regex = Regex::new(r"Author: (?P<name>.*) <(?P<email>.*)>").unwrap();
-    static ref DATE_RE: Regex = Regex::new(r"Date:\s+(?P<date>.*)").unwrap();
-    static ref MERGE_RE: Regex = Regex::new(r"Merge:\s+(?P<merges>.*)").unwrap();
-    static ref FILE_STAT_RE: Regex = Regex::new(r"(\d+)\s+(\d+)\s+(.*)").unwrap();
+    static ref AUTHOR_RE: Regex = Regex::new(r"^Author:\s+(?P<name>.*) <(?P<email>.*)>").unwrap();
+    static ref DATE_RE: Regex = Regex::new(r"^(Author)?Date:\s+(?P<date>.*)").unwrap();
+    static ref COMMITTER_RE: Regex =
+        Regex::new(r"^Commit:\s+(?P<name>.*) <(?P<email>.*)>").unwrap();
+    static ref COMMIT_DATE_RE: Regex = Regex::new(r"^CommitDate:\s+(?P<date>.*)").unwrap();
+    static ref MERGE_RE: Regex = Regex::new(r"^Merge:\s+(?P<merges>.*)").unwrap();
+    static ref CO_AUTHOR_RE: Regex =
+        Regex::new(r"(?i)^co-authored-by:\s*(?P<name>.*?)\s*<(?P<email>.*)>").unwrap();
+    static ref FILE_STAT_RE: Regex = Regex::new(r"^(\d+|-)\s+(\d+|-)\s+(.*)").unwrap();
 }
 
 /// Parse git log --numstat content
@@ -36,33 +43,41 @@ lazy_static! {
 /// 2       2       config/app_log/summary.ts
 /// ```
 pub fn parse_from_str(s: &str) -> Result<Vec<crate::Numstat>> {
-    // Split into block of commits
-    let blocks = s
-        .split("\ncommit ")
-        .filter(|s| !s.trim().is_empty())
-        .map(|s| {
-            if s.trim().starts_with("commit ") {
-                s.trim().to_string()
-            } else {
-                format!("commit {}", s.trim())
+    Ok(parse_from_str_with(s, Mode::Lenient)?.numstats)
+}
+
+/// Parse git log --numstat content, see [`Mode`] for the handling of invalid commits
+pub fn parse_from_str_with(s: &str, mode: Mode) -> Result<Parsed> {
+    // Split into block of commits, keep the line number of each block
+    let mut blocks: Vec<(usize, String)> = vec![];
+    for (idx, line) in s.lines().enumerate() {
+        match blocks.last_mut() {
+            Some((_, block)) if !line.starts_with("commit ") => {
+                block.push_str(line);
+                block.push('\n');
             }
-        })
-        .collect::<Vec<String>>();
+            _ => blocks.push((idx + 1, format!("{}\n", line))),
+        }
+    }
 
-    Ok(blocks
+    let results = blocks
         .par_iter()
-        .filter_map(|block| parse_block(block).ok())
-        .collect())
+        .filter(|(_, block)| !block.trim().is_empty())
+        .map(|(line, block)| parse_block(block, *line))
+        .collect::<Vec<_>>();
+
+    Ok(Parsed::collect(results, mode)?)
 }
 
-fn parse_block(block: &str) -> Result<crate::Numstat> {
-    let lines = block.lines();
+/// Parse a single commit, `start_line` is the line number of the block in the input
+pub(crate) fn parse_block(block: &str, start_line: usize) -> Result<crate::Numstat, Error> {
     let mut numstat = crate::Numstat::default();
-
-    let default_date = numstat.date;
+    let mut has_commit_date = false;
 
     // Parse line by line
-    for line in lines {
+    for (idx, line) in block.lines().enumerate() {
+        let line_no = start_line + idx;
+
         // Skip empty line
         if line.is_empty() {
             continue;
@@ -71,24 +86,41 @@ fn parse_block(block: &str) -> Result<crate::Numstat> {
         // Parse commit
         if line.starts_with("commit ") {
             let commit = line
-                .split(' ')
-                .nth(1)
-                .ok_or_else(|| anyhow!("Invalid commit line: {}", line))?;
+                .strip_prefix("commit ")
+                .and_then(|s| s.split_whitespace().next())
+                .unwrap_or_default();
 
             // BUG-022: Check commit length before setting field
             if commit.len() < 15 {
-                debug!("Skipping invalid commit: {} (length < 15)", commit);
+                debug!("Invalid commit: {} (length < 15)", commit);
                 debug!("{}", block);
-                break;
+                return Err(Error::InvalidCommit {
+                    line: line_no,
+                    content: line.to_string(),
+                });
             }
 
             numstat.commit = commit.to_string();
             debug!("commit: {}", commit);
 
+            // commit bbb7c8e78f07cd06dc015c7139cb174285cd6a8c 32e30ab...\trefs/heads/master (HEAD -> master)
+            numstat.parents = line
+                .split(['\t', '('])
+                .next()
+                .unwrap_or_default()
+                .split_whitespace()
+                .skip(2)
+                .map(|s| s.to_string())
+                .collect();
+
+            // commit bbb7c8e78f07cd06dc015c7139cb174285cd6a8c\trefs/heads/master (tag: v1.0.24+demo)
+            if let Some(source) = line.split('\t').nth(1) {
+                numstat.source = source.split(" (").next().unwrap_or_default().to_string();
+            }
+
             // commit bbb7c8e78f07cd06dc015c7139cb174285cd6a8c (tag: v1.0.24+demo, HEAD -> master, origin/master)
             // BUG-012: Handle missing capture group safely
-            let branch_tag_re = regex::Regex::new(r"\((.+)\)").unwrap();
-            if let Some(brand_tag) = branch_tag_re.captures(line).and_then(|c| c.get(1)) {
+            if let Some(brand_tag) = BRANCH_TAG_RE.captures(line).and_then(|c| c.get(1)) {
                 let brand_or_tag = brand_tag.as_str();
                 numstat.tags = brand_or_tag
                     .split(',')
@@ -110,44 +142,34 @@ fn parse_block(block: &str) -> Result<crate::Numstat> {
         }
 
         // Parse author: Author: Duyet Le <me@duyet.net>
-        // TODO: Parse multiple authors
         // BUG-013: Handle missing captures gracefully
-        let author_re = regex::Regex::new(r"Author: (?P<name>.*) <(?P<email>.*)>").unwrap();
-        if let Some(captures) = author_re.captures(line) {
-            numstat.author.full = line.trim_start_matches("Author: ").to_string();
-            numstat.author.name = captures
-                .name("name")
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_else(|| "Unknown".to_string());
-            numstat.author.email = captures
-                .name("email")
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_else(|| "unknown@example.com".to_string());
+        if let Some(captures) = AUTHOR_RE.captures(line) {
+            numstat.author = parse_identity(&captures);
             continue;
         }
 
-        // Parse date
+        // Parse author date: `Date:` or `AuthorDate:` with --format=fuller
         // BUG-014: Handle missing date capture gracefully
-        let date_re = regex::Regex::new(r"Date:\s+(?P<date>.*)").unwrap();
-        if let Some(captures) = date_re.captures(line) {
-            // TODO: there are a bug that the commit message contains `Date: `
-            // To workaround, we will skip if the date is already parsed
-            if numstat.date != default_date {
-                continue;
-            }
+        if let Some(captures) = DATE_RE.captures(line) {
+            numstat.date = parse_date(&captures, line_no)?;
+            continue;
+        }
 
-            if let Some(date_match) = captures.name("date") {
-                let date = date_match.as_str();
-                numstat.date = DateTime::parse_from_rfc2822(date)
-                    .with_context(|| format!("Parsing block `{}`", block))
-                    .with_context(|| format!("Parsing date `{}`", date))?;
-            }
+        // Parse committer: Commit: Duyet Le <me@duyet.net>
+        if let Some(captures) = COMMITTER_RE.captures(line) {
+            numstat.committer = parse_identity(&captures);
+            continue;
+        }
+
+        // Parse commit date
+        if let Some(captures) = COMMIT_DATE_RE.captures(line) {
+            numstat.commit_date = parse_date(&captures, line_no)?;
+            has_commit_date = true;
             continue;
         }
 
         // Merges
-        let merge_re = regex::Regex::new(r"Merge:\s+(?P<merges>.*)").unwrap();
-        if let Some(captures) = merge_re.captures(line) {
+        if let Some(captures) = MERGE_RE.captures(line) {
             if let Some(merges) = captures.name("merges") {
                 numstat.merges = merges.as_str().split(' ').map(|s| s.to_string()).collect();
             }
@@ -160,15 +182,22 @@ fn parse_block(block: &str) -> Result<crate::Numstat> {
         if line.starts_with("    ") {
             numstat.message.push_str(line.trim());
             numstat.message.push('\n');
+
+            // Co-authored-by: Duyet Le <me@duyet.net>
+            if let Some(author) = parse_co_author(line.trim()) {
+                numstat.co_authors.push(author);
+            }
             continue;
         }
 
         // Parse stats using regex
         // Each line contains two \t separated numbers and a path
         // 20       2       config/app_log/summary.ts
+        // Binary files are reported with `-` instead of numbers
+        // -        -       assets/logo.png
         // BUG-015 & BUG-016: Handle file stats regex captures safely
-        let file_stat_re = regex::Regex::new(r"(\d+)\s+(\d+)\s+(.*)").unwrap();
-        if let Some(captures) = file_stat_re.captures(line) {
+        if let Some(captures) = FILE_STAT_RE.captures(line) {
+            let is_binary = captures.get(1).is_some_and(|m| m.as_str() == "-");
             let added = captures
                 .get(1)
                 .and_then(|m| m.as_str().parse::<u32>().ok())
@@ -182,31 +211,183 @@ fn parse_block(block: &str) -> Result<crate::Numstat> {
                 .map(|m| m.as_str().to_string())
                 .unwrap_or_else(|| "unknown".to_string());
 
-            // Parse extension
-            // BUG-025: Use explicit default for extension parsing
-            let extension = path.split('.').next_back().unwrap_or("unknown").to_string();
-            // .github/workflows/{ci.yaml => rust-test.yaml}
-            let extension = extension.to_lowercase().trim_end_matches('}').to_string();
-
-            let stat = crate::numstat::Stat {
-                added,
-                deleted,
-                path,
-                extension,
-            };
+            let stat = new_stat(added, deleted, path, is_binary);
 
             numstat.stats.push(stat);
             continue;
         }
     }
 
+    // Without --format=fuller, the committer is the author
+    if numstat.committer.email.is_empty() {
+        numstat.committer = numstat.author.clone();
+    }
+    if !has_commit_date {
+        numstat.commit_date = numstat.date;
+    }
+
     if numstat.commit.is_empty() {
-        return Err(anyhow!("cannot parse this block commit: {}", block));
+        return Err(Error::InvalidCommit {
+            line: start_line,
+            content: block.lines().next().unwrap_or_default().to_string(),
+        });
     }
 
     Ok(numstat)
 }
 
+/// Author or committer from the `name` and `email` captures
+fn parse_identity(captures: &regex::Captures) -> crate::numstat::Author {
+    let name = captures
+        .name("name")
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let email = captures
+        .name("email")
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| "unknown@example.com".to_string());
+
+    crate::numstat::Author {
+        full: format!("{} <{}>", name, email),
+        name,
+        email,
+    }
+}
+
+/// RFC 2822 date from the `date` capture
+fn parse_date(captures: &regex::Captures, line_no: usize) -> Result<DateTime<FixedOffset>, Error> {
+    let date = captures
+        .name("date")
+        .map(|m| m.as_str())
+        .unwrap_or_default();
+
+    DateTime::parse_from_rfc2822(date).map_err(|_| Error::InvalidDate {
+        line: line_no,
+        date: date.to_string(),
+    })
+}
+
+/// Parse a `Co-authored-by: Name <email>` trailer of the commit message
+pub(crate) fn parse_co_author(line: &str) -> Option<crate::numstat::Author> {
+    let captures = CO_AUTHOR_RE.captures(line)?;
+    let name = captures.name("name")?.as_str().to_string();
+    let email = captures.name("email")?.as_str().to_string();
+
+    Some(crate::numstat::Author {
+        full: format!("{} <{}>", name, email),
+        name,
+        email,
+    })
+}
+
+/// Build a `Stat` from a numstat entry, `path` is in git's notation
+pub(crate) fn new_stat(
+    added: u32,
+    deleted: u32,
+    path: String,
+    is_binary: bool,
+) -> crate::numstat::Stat {
+    // Renamed or copied files
+    // .github/workflows/{ci.yaml => rust-test.yaml}
+    let (old_path, new_path, is_rename) = match parse_rename(&path) {
+        Some((old_path, new_path)) => (old_path, new_path, true),
+        None => (path.clone(), path.clone(), false),
+    };
+
+    // Parse extension from the destination path
+    // BUG-025: Use explicit default for extension parsing
+    let extension = new_path
+        .split('.')
+        .next_back()
+        .unwrap_or("unknown")
+        .to_lowercase();
+
+    crate::numstat::Stat {
+        added,
+        deleted,
+        path,
+        old_path,
+        new_path,
+        extension,
+        is_binary,
+        is_rename,
+    }
+}
+
+/// Split the rename notation of `git log --numstat` into old and new paths
+///
+/// ```txt
+/// .github/workflows/{ci.yaml => rust-test.yaml}
+/// src/{ => utils}/git.rs
+/// old/a.rs => new/a.rs
+/// ```
+fn parse_rename(path: &str) -> Option<(String, String)> {
+    if let (Some(start), Some(end)) = (path.find('{'), path.rfind('}')) {
+        let (from, to) = path[start + 1..end].split_once(" => ")?;
+        let (prefix, suffix) = (&path[..start], &path[end + 1..]);
+
+        // Empty side of the braces leaves a double slash: `src/{ => utils}/git.rs`
+        let join = |part: &str| match suffix.strip_prefix('/') {
+            Some(rest) if part.is_empty() && (prefix.is_empty() || prefix.ends_with('/')) => {
+                format!("{}{}", prefix, rest)
+            }
+            _ => format!("{}{}{}", prefix, part, suffix),
+        };
+
+        return Some((join(from), join(to)));
+    }
+
+    path.split_once(" => ")
+        .map(|(from, to)| (from.to_string(), to.to_string()))
+}
+
+/// Path of a renamed file as printed by git, the inverse of [`parse_rename`]:
+/// the common leading and trailing folders are kept outside of the braces
+///
+/// ```text
+/// src/git.rs, src/utils/git.rs => src/{ => utils}/git.rs
+/// ```
+#[cfg_attr(not(feature = "native"), allow(dead_code))]
+pub(crate) fn format_rename<'a>(old: &'a str, new: &'a str) -> String {
+    let (a, b) = (old.as_bytes(), new.as_bytes());
+
+    // Common prefix, up to the last slash
+    let mut prefix = 0;
+    let mut i = 0;
+    while i < a.len() && i < b.len() && a[i] == b[i] {
+        if a[i] == b'/' {
+            prefix = i + 1;
+        }
+        i += 1;
+    }
+
+    // Common suffix, from the first slash, it can share the slash ending the prefix
+    let min = prefix.saturating_sub(1);
+    let mut suffix = 0;
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > min && j > min && a[i - 1] == b[j - 1] {
+        if a[i - 1] == b'/' {
+            suffix = a.len() - (i - 1);
+        }
+        i -= 1;
+        j -= 1;
+    }
+
+    if prefix + suffix == 0 {
+        return format!("{} => {}", old, new);
+    }
+
+    // Both are cut next to a slash, so on char boundaries
+    let mid = |s: &'a str| &s[prefix..(s.len() - suffix).max(prefix)];
+    format!(
+        "{}{{{} => {}}}{}",
+        &old[..prefix],
+        mid(old),
+        mid(new),
+        &old[old.len() - suffix..]
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,6 +504,11 @@ mod tests {
             ".github/workflows/{ci.yaml => rust-test.yaml}"
         );
         assert_eq!(fourth.stats[1].extension, "yaml");
+        assert!(fourth.stats[1].is_rename);
+        assert_eq!(fourth.stats[1].old_path, ".github/workflows/ci.yaml");
+        assert_eq!(fourth.stats[1].new_path, ".github/workflows/rust-test.yaml");
+        assert!(!fourth.stats[0].is_rename);
+        assert_eq!(fourth.stats[0].new_path, fourth.stats[0].path);
 
         let last = &results[6];
         assert_eq!(last.commit, "68a776b89c865a91a05047af6d8f2db2f03859d0");
@@ -350,4 +536,229 @@ mod tests {
         assert_eq!(last.stats[3].path, "tests/cli_build.rs");
         assert_eq!(last.stats[3].extension, "rs");
     }
+
+    #[test]
+    fn test_numstat_binary_files() {
+        let raw = indoc! {"
+            commit 8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e
+            Author: Duyet Le <me@duyet.net>
+            Date:   Wed, 11 Jan 2023 11:22:17 +0700
+
+                feat: add logo
+
+            -       -       assets/logo.png
+            12      0       README.md
+        "};
+
+        let results = parse_from_str(raw).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let stats = &results[0].stats;
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].path, "assets/logo.png");
+        assert_eq!(stats[0].extension, "png");
+        assert_eq!(stats[0].added, 0);
+        assert_eq!(stats[0].deleted, 0);
+        assert!(stats[0].is_binary);
+        assert_eq!(stats[1].added, 12);
+        assert!(!stats[1].is_binary);
+    }
+
+    #[test]
+    fn test_parse_rename() {
+        assert_eq!(parse_rename("src/main.rs"), None);
+        assert_eq!(
+            parse_rename(".github/workflows/{ci.yaml => rust-test.yaml}"),
+            Some((
+                ".github/workflows/ci.yaml".to_string(),
+                ".github/workflows/rust-test.yaml".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_rename("src/{ => utils}/git.rs"),
+            Some(("src/git.rs".to_string(), "src/utils/git.rs".to_string()))
+        );
+        assert_eq!(
+            parse_rename("{old => new}/a.rs"),
+            Some(("old/a.rs".to_string(), "new/a.rs".to_string()))
+        );
+        assert_eq!(
+            parse_rename("{ => lib}/a.rs"),
+            Some(("a.rs".to_string(), "lib/a.rs".to_string()))
+        );
+        // Only the braces are collapsed, not the slashes elsewhere in the path
+        assert_eq!(
+            parse_rename("src//{a => b}.rs"),
+            Some(("src//a.rs".to_string(), "src//b.rs".to_string()))
+        );
+        assert_eq!(
+            parse_rename("old/a.rs => new/a.rs"),
+            Some(("old/a.rs".to_string(), "new/a.rs".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_format_rename() {
+        let cases = [
+            ("src/git.rs", "src/utils/git.rs", "src/{ => utils}/git.rs"),
+            ("src/utils/git.rs", "src/git.rs", "src/{utils => }/git.rs"),
+            (
+                ".github/workflows/ci.yaml",
+                ".github/workflows/rust-test.yaml",
+                ".github/workflows/{ci.yaml => rust-test.yaml}",
+            ),
+            ("old/a.rs", "new/a.rs", "{old => new}/a.rs"),
+            ("a.rs", "b.rs", "a.rs => b.rs"),
+        ];
+
+        for (old, new, path) in cases {
+            assert_eq!(format_rename(old, new), path);
+            assert_eq!(parse_rename(path), Some((old.to_string(), new.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_numstat_co_authors() {
+        let raw = indoc! {"
+            commit 8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e
+            Author: Duyet Le <me@duyet.net>
+            Date:   Wed, 11 Jan 2023 11:22:17 +0700
+
+                feat: pair programming
+
+                Co-authored-by: Duet <duet@duyet.net>
+                co-authored-by: renovate[bot] <29139614+renovate[bot]@users.noreply.github.com>
+
+            12      0       README.md
+        "};
+
+        let results = parse_from_str(raw).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let co_authors = &results[0].co_authors;
+        assert_eq!(co_authors.len(), 2);
+        assert_eq!(co_authors[0].name, "Duet");
+        assert_eq!(co_authors[0].email, "duet@duyet.net");
+        assert_eq!(co_authors[0].full, "Duet <duet@duyet.net>");
+        assert_eq!(co_authors[1].name, "renovate[bot]");
+        assert_eq!(results[0].author.name, "Duyet Le");
+    }
+
+    #[test]
+    fn test_numstat_format_fuller() {
+        let raw = indoc! {"
+            commit 8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e
+            Author:     Duyet Le <me@duyet.net>
+            AuthorDate: Wed, 11 Jan 2023 11:22:17 +0700
+            Commit:     GitHub <noreply@github.com>
+            CommitDate: Thu, 12 Jan 2023 08:00:00 +0000
+
+                feat: rebased
+
+                Date: this line is part of the message
+
+            12      0       README.md
+        "};
+
+        let results = parse_from_str(raw).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let numstat = &results[0];
+        assert_eq!(numstat.author.name, "Duyet Le");
+        assert_eq!(numstat.author.full, "Duyet Le <me@duyet.net>");
+        assert_eq!(numstat.date.to_rfc2822(), "Wed, 11 Jan 2023 11:22:17 +0700");
+        assert_eq!(numstat.committer.name, "GitHub");
+        assert_eq!(numstat.committer.email, "noreply@github.com");
+        assert_eq!(
+            numstat.commit_date.to_rfc2822(),
+            "Thu, 12 Jan 2023 08:00:00 +0000"
+        );
+        assert_eq!(numstat.stats.len(), 1);
+    }
+
+    #[test]
+    fn test_numstat_committer_defaults_to_author() {
+        let raw = indoc! {"
+            commit 8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e
+            Author: Duyet Le <me@duyet.net>
+            Date:   Wed, 11 Jan 2023 11:22:17 +0700
+
+                feat: init
+        "};
+
+        let results = parse_from_str(raw).unwrap();
+        assert_eq!(results[0].committer.email, "me@duyet.net");
+        assert_eq!(results[0].commit_date, results[0].date);
+    }
+
+    #[test]
+    fn test_numstat_source() {
+        let raw = "commit 8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e\trefs/heads/main (HEAD -> main, tag: v1, origin/main)
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 11 Jan 2023 11:22:17 +0700
+
+    feat: init
+";
+
+        let results = parse_from_str(raw).unwrap();
+        assert_eq!(
+            results[0].commit,
+            "8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e"
+        );
+        assert_eq!(results[0].source, "refs/heads/main");
+        assert_eq!(results[0].tags, vec!["v1"]);
+        assert_eq!(results[0].branches, vec!["origin/main"]);
+    }
+
+    #[test]
+    fn test_numstat_parents() {
+        let raw = "commit 8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e 32e30ab1e1b5c3d6d2c5a3e1a1c0f9b1d2c3e4f5 bbb7c8e78f07cd06dc015c7139cb174285cd6a8c\trefs/heads/main (HEAD -> main)
+Merge: 32e30ab bbb7c8e
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 11 Jan 2023 11:22:17 +0700
+
+    Merge branch 'feature'
+
+commit 32e30ab1e1b5c3d6d2c5a3e1a1c0f9b1d2c3e4f5 (tag: v1)
+Author: Duyet Le <me@duyet.net>
+Date:   Wed, 11 Jan 2023 11:22:17 +0700
+
+    feat: init
+";
+
+        let results = parse_from_str(raw).unwrap();
+        assert_eq!(
+            results[0].commit,
+            "8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e"
+        );
+        assert_eq!(
+            results[0].parents,
+            vec![
+                "32e30ab1e1b5c3d6d2c5a3e1a1c0f9b1d2c3e4f5",
+                "bbb7c8e78f07cd06dc015c7139cb174285cd6a8c"
+            ]
+        );
+        assert_eq!(results[0].source, "refs/heads/main");
+
+        // The root commit, without the source
+        assert!(results[1].parents.is_empty());
+        assert_eq!(results[1].tags, vec!["v1"]);
+    }
+
+    #[test]
+    fn test_numstat_merge_in_message() {
+        let raw = indoc! {"
+            commit 8e5d8b4a3c2c0e6b0b7a2f0d9c1e3f4a5b6c7d8e
+            Author: Duyet Le <me@duyet.net>
+            Date:   Wed, 11 Jan 2023 11:22:17 +0700
+
+                fix: parse the Merge: header
+
+            1       1       src/git.rs
+        "};
+
+        let results = parse_from_str(raw).unwrap();
+        assert!(results[0].merges.is_empty());
+        assert_eq!(results[0].message, "fix: parse the Merge: header\n");
+    }
 }
@@ -0,0 +1,27 @@
+//! Helpers shared by the unit tests reading a local git repository
+
+use std::path::Path;
+use std::process::Command;
+
+/// `git <args>` in `dir` with a fixed identity
+pub fn git(dir: &Path, args: &[&str]) {
+    git_with_env(dir, args, &[]);
+}
+
+/// Same as [`git`] with the environment variables, e.g. `GIT_COMMITTER_DATE`
+pub fn git_with_env(dir: &Path, args: &[&str], envs: &[(&str, &str)]) {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Duyet Le", "-c", "user.email=me@duyet.net"])
+        .args(args)
+        .envs(envs.iter().copied())
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "git {:?}: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}